@@ -3,23 +3,39 @@
 use super::*;
 
 use crate::Pallet as IntegrationWithReclaim;
-use frame_benchmarking::{account, benchmarks};
-use frame_support::{assert_ok, BoundedVec};
-use frame_system::RawOrigin;
-use pallet_reclaim::{ClaimInfo, CompleteClaimData, Pallet as Reclaim, SignedClaim, Witness};
+use frame_benchmarking::{account, benchmarks, BenchmarkError};
+use frame_support::{
+	assert_ok,
+	sp_io::crypto::{sr25519_generate, sr25519_sign},
+	sp_runtime::traits::{Bounded, Hash, IdentifyAccount, Zero},
+	traits::Currency,
+	BoundedVec,
+};
+use frame_system::{pallet_prelude::BlockNumberFor, RawOrigin};
+use pallet_reclaim::{
+	proof_builder, ClaimInfo, CompleteClaimData, Pallet as Reclaim, SignedClaim, Witness,
+};
 use scale_info::prelude::{fmt::Debug, format, string::String, vec, vec::Vec};
-use sp_core::ConstU32;
+use sp_core::{crypto::KeyTypeId, sr25519, ConstU32};
 
 benchmarks! {
 	where_clause {
 		where
 			T: Config + pallet_reclaim::Config,
+			T::AuthorizationSigner: From<sr25519::Public>,
+			T::AuthorizationSignature: From<sr25519::Signature>,
 	}
 // Benchmark for the verify_user extrinsic
   verify_user{
 	// Set up test account
 	let source_account_id: T::AccountId = account("acc1", 0,0);
-	assert_ok!(Reclaim::<T>::init(RawOrigin::Signed(source_account_id.clone()).into()));
+	<T as pallet_reclaim::Config>::Currency::make_free_balance_be(
+		&source_account_id,
+		pallet_reclaim::BalanceOf::<T>::max_value() / 2_u32.into(),
+	);
+	let init_origin = <T as pallet_reclaim::Config>::InitOrigin::try_successful_origin()
+		.map_err(|_| BenchmarkError::Weightless)?;
+	assert_ok!(Reclaim::<T>::init(init_origin, source_account_id.clone()));
 
 		// Prepare a witness address
 		let addr = hex::decode("244897572368eadf65bfbc5aec98d8e5443a9072").unwrap();
@@ -76,6 +92,7 @@ benchmarks! {
 			claimInfo: claim_info,
 			signedClaim: signed_claim,
 		};
+		let provider_hash = proof.claimInfo.provider_hash().unwrap();
 
   } : {
 		// Call verify_user extrinsic
@@ -86,7 +103,102 @@ benchmarks! {
   }
   verify{
 	// Verify the result of the verification
-	assert_eq!(IntegrationWithReclaim::<T>::account_verified(source_account_id), Some(true));
+	assert!(IntegrationWithReclaim::<T>::is_verified_for(&source_account_id, &provider_hash));
+  }
+// Benchmark for the reassign_identity extrinsic
+  reassign_identity{
+	let origin = T::IdentityAdminOrigin::try_successful_origin()
+		.map_err(|_| BenchmarkError::Weightless)?;
+	let identity = T::Hashing::hash_of(&String::from("76561199601812329"));
+	let provider_hash = [0_u8; 32];
+	// The previous account holds a verification to remove
+	let previous: T::AccountId = account("acc1", 0,0);
+	UniqueIdentities::<T>::insert(identity, IdentityBinding {
+		account_id: previous.clone(),
+		provider_hash,
+	});
+	let now = frame_system::Pallet::<T>::block_number();
+	AccountVerified::<T>::insert(&previous, provider_hash, VerificationRecord {
+		verified_at: now,
+		expires_at: now + T::VerificationTtl::get(),
+		epoch: 1,
+		claim_identifier: [0_u8; 32],
+		witnesses: BoundedVec::default(),
+	});
+	let account_id: T::AccountId = account("acc2", 0,0);
+  }: _<T::RuntimeOrigin>(origin, identity, account_id.clone())
+  verify{
+	assert_eq!(IntegrationWithReclaim::<T>::identity_owner(identity), Some(account_id));
+	assert!(!AccountVerified::<T>::contains_key(&previous, provider_hash));
+  }
+// Benchmark for the verify_user_unsigned extrinsic
+  verify_user_unsigned{
+	let account_id: T::AccountId = account("acc1", 0,0);
+	<T as pallet_reclaim::Config>::Currency::make_free_balance_be(
+		&account_id,
+		pallet_reclaim::BalanceOf::<T>::max_value() / 2_u32.into(),
+	);
+	let init_origin = <T as pallet_reclaim::Config>::InitOrigin::try_successful_origin()
+		.map_err(|_| BenchmarkError::Weightless)?;
+	assert_ok!(Reclaim::<T>::init(init_origin, account_id.clone()));
+	assert_ok!(Reclaim::<T>::add_epoch(
+		RawOrigin::Signed(account_id.clone()).into(),
+		proof_builder::witnesses(1),
+		1
+	));
+	// Generate a proof for the account
+	let context_address = format!("0x{}", hex::encode(account_id.encode()));
+	let claim_info = ClaimInfo {
+		provider: String::from("http"),
+		parameters: String::from("{}"),
+		context: proof_builder::claim_context(&context_address, "76561199601812329"),
+	};
+	let proof = proof_builder::build_proof(&Reclaim::<T>::epochs(1).unwrap(), claim_info, 1712174155);
+	let provider_hash = proof.claimInfo.provider_hash().unwrap();
+  }: _(RawOrigin::None, account_id.clone(), proof)
+  verify{
+	assert!(IntegrationWithReclaim::<T>::is_verified_for(&account_id, &provider_hash));
+  }
+// Benchmark for the verify_user_for extrinsic
+  verify_user_for{
+	let relayer: T::AccountId = account("acc1", 0,0);
+	<T as pallet_reclaim::Config>::Currency::make_free_balance_be(
+		&relayer,
+		pallet_reclaim::BalanceOf::<T>::max_value() / 2_u32.into(),
+	);
+	<T as Config>::Currency::make_free_balance_be(
+		&relayer,
+		BalanceOf::<T>::max_value() / 2_u32.into(),
+	);
+	let init_origin = <T as pallet_reclaim::Config>::InitOrigin::try_successful_origin()
+		.map_err(|_| BenchmarkError::Weightless)?;
+	assert_ok!(Reclaim::<T>::init(init_origin, relayer.clone()));
+	assert_ok!(Reclaim::<T>::add_epoch(
+		RawOrigin::Signed(relayer.clone()).into(),
+		proof_builder::witnesses(1),
+		1
+	));
+	// Generate a key for the target and a proof it authorizes
+	let key_type = KeyTypeId(*b"rclm");
+	let public = sr25519_generate(key_type, None);
+	let target = T::AuthorizationSigner::from(public).into_account();
+	let context_address = format!("0x{}", hex::encode(target.encode()));
+	let claim_info = ClaimInfo {
+		provider: String::from("http"),
+		parameters: String::from("{}"),
+		context: proof_builder::claim_context(&context_address, "76561199601812329"),
+	};
+	let proof = proof_builder::build_proof(&Reclaim::<T>::epochs(1).unwrap(), claim_info, 1712174155);
+	let provider_hash = proof.claimInfo.provider_hash().unwrap();
+	let claim_identifier = IntegrationWithReclaim::<T>::claim_identifier(&proof).unwrap();
+	let genesis_hash = frame_system::Pallet::<T>::block_hash(BlockNumberFor::<T>::zero());
+	let message = authorization_message(&genesis_hash, &claim_identifier, 0);
+	let authorization: T::AuthorizationSignature =
+		sr25519_sign(key_type, &public, &message).unwrap().into();
+  }: _(RawOrigin::Signed(relayer), target.clone(), proof, authorization)
+  verify{
+	assert!(IntegrationWithReclaim::<T>::is_verified_for(&target, &provider_hash));
+	assert_eq!(IntegrationWithReclaim::<T>::authorization_nonce(&target), 1);
   }
 
 
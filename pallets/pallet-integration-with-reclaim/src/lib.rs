@@ -10,9 +10,65 @@ mod tests;
 
 #[cfg(feature = "runtime-benchmarks")]
 mod benchmarking;
+pub mod migrations;
+pub mod traits;
 pub mod weights;
 
-use pallet_reclaim::{traits::ReclaimVerifier, Proof};
+use codec::{Decode, Encode, MaxEncodedLen};
+use core::marker::PhantomData;
+use frame_support::{
+	pallet_prelude::ConstU32,
+	traits::{Currency, EnsureOrigin, Get, ReservableCurrency, StorageVersion},
+	weights::Weight,
+	BoundedVec,
+};
+use pallet_reclaim::{
+	traits::{OnClaimRevoked, ProofCall, ReclaimVerifier},
+	Proof, ProviderHash, VerifiedClaim,
+};
+use traits::VerificationInspect;
+use scale_info::prelude::vec::Vec;
+
+/// Domain tag authorization messages start with, so that they can't be replayed as signatures
+/// meant for another pallet.
+pub const AUTHORIZATION_DOMAIN: &[u8] = b"pallet-integration-with-reclaim/verify_user_for";
+
+/// Returns the message an account signs to authorize the relayed verification of a claim with
+/// its current authorization nonce, on the chain with `genesis_hash`.
+pub fn authorization_message<Hash: Encode>(
+	genesis_hash: &Hash,
+	claim_identifier: &[u8; 32],
+	nonce: u64,
+) -> Vec<u8> {
+	(AUTHORIZATION_DOMAIN, genesis_hash, claim_identifier, nonce).encode()
+}
+
+/// Record of a successful verification, valid until `expires_at` while none of its witnesses is
+/// revoked.
+#[derive(Encode, Decode, Eq, PartialEq, Clone, scale_info::TypeInfo, MaxEncodedLen, Debug)]
+pub struct VerificationRecord<BlockNumber> {
+	pub verified_at: BlockNumber,                       // Block in which the account was verified
+	pub expires_at: BlockNumber,                        // Block from which the verification is no longer valid
+	pub epoch: u64,                                     // Epoch the verified claim was made in
+	pub claim_identifier: [u8; 32],                     // Identifier of the verified claim
+	pub witnesses: BoundedVec<[u8; 20], ConstU32<100>>, // Witnesses that attested the claim
+}
+
+/// Account verified with a relayed claim, tracked with a deposit reserved from the relayer until
+/// the verification is revoked, expires or is replaced.
+#[derive(Encode, Decode, Eq, PartialEq, Clone, scale_info::TypeInfo, MaxEncodedLen, Debug)]
+pub struct RelayedClaim<AccountId, Balance> {
+	pub account_id: AccountId, // Account verified with the claim
+	pub relayer: AccountId,    // Account that relayed the verification
+	pub deposit: Balance,      // Deposit reserved from the relayer
+}
+
+/// Account a unique identity is bound to, with the provider the identity was proven for.
+#[derive(Encode, Decode, Eq, PartialEq, Clone, scale_info::TypeInfo, MaxEncodedLen, Debug)]
+pub struct IdentityBinding<AccountId> {
+	pub account_id: AccountId,       // Account the identity is bound to
+	pub provider_hash: ProviderHash, // Provider the identity was proven for
+}
 
 #[frame_support::pallet]
 pub mod pallet {
@@ -22,46 +78,615 @@ pub mod pallet {
 	use super::*;
 	use frame_support::pallet_prelude::{DispatchResult, StorageMap, *};
 	use frame_system::pallet_prelude::*;
+	use frame_support::sp_runtime::traits::{
+		Bounded, Hash, IdentifyAccount, Saturating, Verify, Zero,
+	};
+
+	pub type VerificationRecordOf<T> = VerificationRecord<BlockNumberFor<T>>;
+
+	/// Balance of the currency relayer deposits are reserved in.
+	pub type BalanceOf<T> =
+		<<T as Config>::Currency as Currency<<T as frame_system::Config>::AccountId>>::Balance;
+
+	pub type RelayedClaimOf<T> =
+		RelayedClaim<<T as frame_system::Config>::AccountId, BalanceOf<T>>;
+
+	/// The in-code storage version.
+	const STORAGE_VERSION: StorageVersion = StorageVersion::new(3);
 
 	#[pallet::pallet]
+	#[pallet::storage_version(STORAGE_VERSION)]
 	pub struct Pallet<T>(_);
-	
+
 	/// Configuration trait for the pallet
 	#[pallet::config]
 	pub trait Config: frame_system::Config {
 		type RuntimeEvent: From<Event<Self>> + IsType<<Self as frame_system::Config>::RuntimeEvent>;
-		type ReclaimVerifier: ReclaimVerifier<Proof>;
+		type ReclaimVerifier: ReclaimVerifier<Self::AccountId, Proof>;
+		/// Number of blocks a verification stays valid for
+		#[pallet::constant]
+		type VerificationTtl: Get<BlockNumberFor<Self>>;
+		/// Extracted parameter that identifies the user behind a proof, e.g. `CLAIM_DATA`.
+		/// `None` allows the same identity to verify any number of accounts.
+		type UniqueIdentityParameter: Get<Option<&'static str>>;
+		/// Origin allowed to reassign an identity to another account
+		type IdentityAdminOrigin: EnsureOrigin<Self::RuntimeOrigin>;
+		/// Priority of unsigned `verify_user_unsigned` transactions
+		#[pallet::constant]
+		type UnsignedPriority: Get<TransactionPriority>;
+		/// Number of blocks an unsigned `verify_user_unsigned` transaction stays valid for
+		#[pallet::constant]
+		type UnsignedLongevity: Get<TransactionLongevity>;
+		/// Signature an account authorizes a relayed verification with
+		type AuthorizationSignature: Verify<Signer = Self::AuthorizationSigner> + Parameter;
+		/// Public key of an `AuthorizationSignature`, identifying the signing account
+		type AuthorizationSigner: IdentifyAccount<AccountId = Self::AccountId>;
+		/// Currency the deposits of relayed verifications are reserved in
+		type Currency: ReservableCurrency<Self::AccountId>;
+		/// Deposit reserved from the relayer of a verification for as long as its claim is tracked
+		#[pallet::constant]
+		type RelayedClaimDeposit: Get<BalanceOf<Self>>;
 		type WeightInfo: WeightInfo;
 	}
 
-	/// Storage map to track verified accounts
+	/// Storage map to track verified accounts per provider
+	#[pallet::storage]
+	pub(super) type AccountVerified<T: Config> = StorageDoubleMap<
+		_,
+		Identity,
+		T::AccountId,
+		Identity,
+		ProviderHash,
+		VerificationRecordOf<T>,
+		OptionQuery,
+	>;
+
+	/// Storage map of the account each unique identity is bound to
+	#[pallet::storage]
+	pub type UniqueIdentities<T: Config> =
+		StorageMap<_, Identity, T::Hash, IdentityBinding<T::AccountId>, OptionQuery>;
+
+	/// Storage map of the claim identifiers already used by unsigned verifications
+	#[pallet::storage]
+	pub(super) type ConsumedClaims<T: Config> =
+		StorageMap<_, Identity, [u8; 32], (), OptionQuery>;
+
+	/// Storage map of the nonce each account authorizes its next relayed verification with
 	#[pallet::storage]
-	#[pallet::getter(fn account_verified)]
-	pub(super) type AccountVerified<T: Config> =
-		StorageMap<_, Identity, T::AccountId, bool, OptionQuery>;
+	#[pallet::getter(fn authorization_nonce)]
+	pub type AuthorizationNonces<T: Config> =
+		StorageMap<_, Blake2_128Concat, T::AccountId, u64, ValueQuery>;
+
+	/// Storage map of the account verified by each relayed claim, with the deposit of its relayer
+	#[pallet::storage]
+	pub(super) type RelayedClaims<T: Config> =
+		StorageMap<_, Identity, [u8; 32], RelayedClaimOf<T>, OptionQuery>;
+
+	/// Raw key of `AccountVerified` the expiry sweeper resumes from
+	#[pallet::storage]
+	pub(super) type SweepCursor<T: Config> =
+		StorageValue<_, BoundedVec<u8, ConstU32<128>>, OptionQuery>;
 
 	/// Events emitted by the pallet
 	#[pallet::event]
 	#[pallet::generate_deposit(pub(super) fn deposit_event)]
 	pub enum Event<T: Config> {
-		/// Emits when a user is verified.
-		UserVerified { account_id: T::AccountId },
+		/// Emits when a user is verified for a provider.
+		UserVerified { account_id: T::AccountId, provider_hash: ProviderHash },
+		/// Emits when the verification of a user for a provider expires.
+		VerificationExpired { account_id: T::AccountId, provider_hash: ProviderHash },
+		/// Emits when an identity is reassigned to another account.
+		IdentityReassigned { identity: T::Hash, account_id: T::AccountId },
+		/// Emits when the verification of a user for a provider is removed because its claim
+		/// was revoked.
+		VerificationRevoked { account_id: T::AccountId, provider_hash: ProviderHash },
 	}
 
 	#[pallet::error]
-	pub enum Error<T> {}
+	pub enum Error<T> {
+		/// The claim identifier is not a 32-byte hex string.
+		InvalidClaimIdentifier,
+		/// The claim context does not declare a valid provider hash.
+		InvalidProviderHash,
+		/// The claim context does not contain the unique identity parameter.
+		MissingIdentityParameter,
+		/// The identity of the claim is already bound to another account.
+		IdentityAlreadyClaimed,
+		/// The context address of the claim is not the account being verified.
+		ContextAddressMismatch,
+		/// The claim was already used by an unsigned verification.
+		ClaimAlreadyConsumed,
+		/// The authorization is not signed by the account being verified over the claim and its
+		/// current nonce.
+		InvalidAuthorization,
+		/// The identity is not bound to any account.
+		UnknownIdentity,
+	}
+
+	#[pallet::hooks]
+	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+		/// Prunes expired verifications with the weight left in the block.
+		fn on_idle(now: BlockNumberFor<T>, remaining_weight: Weight) -> Weight {
+			Self::sweep_expired(now, remaining_weight)
+		}
+	}
 
 	#[pallet::call]
 	impl<T: Config> Pallet<T> {
 		/// Verifies a user based on the provided proof
 		#[pallet::call_index(0)]
-		#[pallet::weight(<T as pallet::Config>::WeightInfo::verify_user())]
-		pub fn verify_user(origin: OriginFor<T>, proof: Proof) -> DispatchResult {
+		#[pallet::weight(Pallet::<T>::verify_weight(
+			proof,
+			<T as pallet::Config>::WeightInfo::verify_user(),
+		))]
+		pub fn verify_user(origin: OriginFor<T>, proof: Proof) -> DispatchResultWithPostInfo {
 			let who = ensure_signed(origin)?;
-			T::ReclaimVerifier::verify_proof(&proof)?;
-			<AccountVerified<T>>::set(&who, Some(true));
-			Self::deposit_event(Event::UserVerified { account_id: who });
+			let overhead = <T as pallet::Config>::WeightInfo::verify_user();
+			Self::do_verify_user(&who, who.clone(), &proof, overhead, true)
+		}
+
+		/// Binds an identity to another account, e.g. to recover from a lost account. The
+		/// verification of the previous account for the provider of the identity is removed, so
+		/// the identity only backs one verified account.
+		#[pallet::call_index(1)]
+		#[pallet::weight(<T as pallet::Config>::WeightInfo::reassign_identity())]
+		pub fn reassign_identity(
+			origin: OriginFor<T>,
+			identity: T::Hash,
+			account_id: T::AccountId,
+		) -> DispatchResult {
+			T::IdentityAdminOrigin::ensure_origin(origin)?;
+			let mut binding =
+				<UniqueIdentities<T>>::get(identity).ok_or(Error::<T>::UnknownIdentity)?;
+			let previous = core::mem::replace(&mut binding.account_id, account_id.clone());
+			let provider_hash = binding.provider_hash;
+			if previous != account_id {
+				if let Some(record) = <AccountVerified<T>>::take(&previous, provider_hash) {
+					Self::release_relayed_claim(&record.claim_identifier, &previous);
+					Self::deposit_event(Event::VerificationRevoked {
+						account_id: previous,
+						provider_hash,
+					});
+				}
+			}
+			<UniqueIdentities<T>>::insert(identity, binding);
+			Self::deposit_event(Event::IdentityReassigned { identity, account_id });
 			Ok(())
 		}
+
+		/// Verifies the account a proof was generated for, without fees.
+		/// Each claim can only be used once this way, and no claim deposit is reserved from the
+		/// account, which may hold no funds yet.
+		#[pallet::call_index(2)]
+		#[pallet::weight(Pallet::<T>::verify_weight(
+			proof,
+			<T as pallet::Config>::WeightInfo::verify_user_unsigned(),
+		))]
+		pub fn verify_user_unsigned(
+			origin: OriginFor<T>,
+			account_id: T::AccountId,
+			proof: Proof,
+		) -> DispatchResultWithPostInfo {
+			ensure_none(origin)?;
+			let claim_identifier = Self::check_unsigned(&account_id, &proof)?;
+			let overhead = <T as pallet::Config>::WeightInfo::verify_user_unsigned();
+			let post_info =
+				Self::do_verify_user(&account_id, account_id.clone(), &proof, overhead, false)?;
+			<ConsumedClaims<T>>::insert(claim_identifier, ());
+			Ok(post_info)
+		}
+
+		/// Verifies `target` with fees paid by the caller, who submits the proof to Reclaim.
+		/// `authorization` is the signature of `target` over the `authorization_message` of the
+		/// claim and its current nonce on this chain. Only `RelayedClaimDeposit` is reserved from
+		/// the caller, instead of the claim deposit of Reclaim, until the verification is revoked,
+		/// expires or is replaced.
+		#[pallet::call_index(3)]
+		#[pallet::weight(Pallet::<T>::verify_weight(
+			proof,
+			<T as pallet::Config>::WeightInfo::verify_user_for(),
+		))]
+		pub fn verify_user_for(
+			origin: OriginFor<T>,
+			target: T::AccountId,
+			proof: Proof,
+			authorization: T::AuthorizationSignature,
+		) -> DispatchResultWithPostInfo {
+			let relayer = ensure_signed(origin)?;
+			let claim_identifier = Self::claim_identifier(&proof)?;
+			let nonce = <AuthorizationNonces<T>>::get(&target);
+			let genesis_hash = frame_system::Pallet::<T>::block_hash(BlockNumberFor::<T>::zero());
+			let message = authorization_message(&genesis_hash, &claim_identifier, nonce);
+			ensure!(authorization.verify(&message[..], &target), Error::<T>::InvalidAuthorization);
+			let overhead = <T as pallet::Config>::WeightInfo::verify_user_for();
+			let post_info =
+				Self::do_verify_user(&relayer, target.clone(), &proof, overhead, false)?;
+			<AuthorizationNonces<T>>::insert(&target, nonce.saturating_add(1));
+			if let Some(previous) = <RelayedClaims<T>>::take(claim_identifier) {
+				T::Currency::unreserve(&previous.relayer, previous.deposit);
+			}
+			let deposit = T::RelayedClaimDeposit::get();
+			T::Currency::reserve(&relayer, deposit)?;
+			let relayed = RelayedClaim { account_id: target, relayer, deposit };
+			<RelayedClaims<T>>::insert(claim_identifier, relayed);
+			Ok(post_info)
+		}
+	}
+
+	#[pallet::validate_unsigned]
+	impl<T: Config> ValidateUnsigned for Pallet<T> {
+		type Call = Call<T>;
+
+		/// Only accepts unsigned verifications of valid, unused proofs generated for the account,
+		/// that dispatch would not reject: the rate limit of the account, a revoked claim or an
+		/// identity bound to another account make the proof invalid.
+		fn validate_unsigned(_source: TransactionSource, call: &Self::Call) -> TransactionValidity {
+			let Call::verify_user_unsigned { account_id, proof } = call else {
+				return InvalidTransaction::Call.into()
+			};
+			let claim_identifier =
+				Self::check_unsigned(account_id, proof).map_err(|error| match error {
+					Error::<T>::ClaimAlreadyConsumed => InvalidTransaction::Stale,
+					Error::<T>::ContextAddressMismatch => InvalidTransaction::BadSigner,
+					_ => InvalidTransaction::BadProof,
+				})?;
+			T::ReclaimVerifier::validate_proof(account_id, proof)
+				.map_err(|_| InvalidTransaction::BadProof)?;
+			Self::check_identity(account_id, proof).map_err(|_| InvalidTransaction::BadProof)?;
+
+			ValidTransaction::with_tag_prefix("ReclaimVerifyUser")
+				.priority(T::UnsignedPriority::get())
+				.and_provides(claim_identifier)
+				.longevity(T::UnsignedLongevity::get())
+				.propagate(true)
+				.build()
+		}
+	}
+
+	impl<T: Config> Pallet<T> {
+		/// Returns the worst-case weight of a verification call, `overhead` on top of the proof
+		/// verification by Reclaim.
+		pub fn verify_weight(proof: &Proof, overhead: Weight) -> Weight {
+			T::ReclaimVerifier::verify_proof_weight(proof).saturating_add(overhead)
+		}
+
+		/// Verifies a proof submitted by `submitter` and records the verification of `who` for its
+		/// provider. The weight reported by Reclaim is returned with `overhead` added, so only
+		/// the checks actually performed are charged.
+		///
+		/// The claim deposit of Reclaim is only reserved from `submitter` if `reserve_deposit` is
+		/// set.
+		fn do_verify_user(
+			submitter: &T::AccountId,
+			who: T::AccountId,
+			proof: &Proof,
+			overhead: Weight,
+			reserve_deposit: bool,
+		) -> DispatchResultWithPostInfo {
+			let verified = if reserve_deposit {
+				T::ReclaimVerifier::verify_proof(submitter, proof)
+			} else {
+				T::ReclaimVerifier::verify_proof_without_deposit(submitter, proof)
+			};
+			let verified = verified.map_err(|mut error| {
+				error.post_info.actual_weight =
+					error.post_info.actual_weight.map(|weight| weight.saturating_add(overhead));
+				error
+			})?;
+			let actual_weight = verified
+				.actual_weight
+				.unwrap_or_else(|| T::ReclaimVerifier::verify_proof_weight(proof))
+				.saturating_add(overhead);
+			let (provider_hash, unbound_identity) = Self::check_identity(&who, proof)?;
+			if let Some(identity) = unbound_identity {
+				let binding = IdentityBinding { account_id: who.clone(), provider_hash };
+				<UniqueIdentities<T>>::insert(identity, binding);
+			}
+			let claim = &proof.signedClaim.claim;
+			let claim_identifier = Self::claim_identifier(proof)?;
+			if let Some(replaced) = <AccountVerified<T>>::get(&who, provider_hash) {
+				Self::release_relayed_claim(&replaced.claim_identifier, &who);
+			}
+			let now = frame_system::Pallet::<T>::block_number();
+			let record = VerificationRecord {
+				verified_at: now,
+				expires_at: now.saturating_add(T::VerificationTtl::get()),
+				epoch: claim.epoch,
+				claim_identifier,
+				witnesses: BoundedVec::truncate_from(
+					T::ReclaimVerifier::attesting_witnesses(proof),
+				),
+			};
+			<AccountVerified<T>>::insert(&who, provider_hash, record);
+			Self::deposit_event(Event::UserVerified { account_id: who, provider_hash });
+			Ok(Some(actual_weight).into())
+		}
+
+		/// Checks that a proof declares its provider and that its identity is not bound to an
+		/// account other than `who`, returning the provider hash and the identity to bind to
+		/// `who`, if not bound yet. An identity is only bound to the account the proof was
+		/// generated for, so a copied proof can't take it.
+		fn check_identity(
+			who: &T::AccountId,
+			proof: &Proof,
+		) -> Result<(ProviderHash, Option<T::Hash>), DispatchError> {
+			let provider_hash =
+				proof.claimInfo.provider_hash().ok_or(Error::<T>::InvalidProviderHash)?;
+			let Some(identity) = Self::unique_identity(proof, &provider_hash)? else {
+				return Ok((provider_hash, None))
+			};
+			match <UniqueIdentities<T>>::get(identity) {
+				Some(binding) => {
+					ensure!(binding.account_id == *who, Error::<T>::IdentityAlreadyClaimed);
+					Ok((provider_hash, None))
+				},
+				None => {
+					Self::ensure_context_address(who, proof)?;
+					Ok((provider_hash, Some(identity)))
+				},
+			}
+		}
+
+		/// Returns the account an identity is bound to.
+		pub fn identity_owner(identity: T::Hash) -> Option<T::AccountId> {
+			<UniqueIdentities<T>>::get(identity).map(|binding| binding.account_id)
+		}
+
+		/// Stops tracking a claim relayed to verify `account_id`, releasing the deposit of its
+		/// relayer
+		fn release_relayed_claim(claim_identifier: &[u8; 32], account_id: &T::AccountId) {
+			let Some(relayed) = <RelayedClaims<T>>::get(claim_identifier) else { return };
+			if relayed.account_id == *account_id {
+				<RelayedClaims<T>>::remove(claim_identifier);
+				T::Currency::unreserve(&relayed.relayer, relayed.deposit);
+			}
+		}
+
+		/// Decodes the identifier of the claim of a proof
+		pub(crate) fn claim_identifier(proof: &Proof) -> Result<[u8; 32], Error<T>> {
+			let identifier = &proof.signedClaim.claim.identifier;
+			let mut claim_identifier = [0_u8; 32];
+			hex::decode_to_slice(identifier.trim_start_matches("0x"), &mut claim_identifier)
+				.map_err(|_| Error::<T>::InvalidClaimIdentifier)?;
+			Ok(claim_identifier)
+		}
+
+		/// Checks that an unused proof was generated for `account_id`, returning its claim
+		/// identifier. The proof itself is checked separately.
+		fn check_unsigned(account_id: &T::AccountId, proof: &Proof) -> Result<[u8; 32], Error<T>> {
+			let claim_identifier = Self::claim_identifier(proof)?;
+			ensure!(
+				!<ConsumedClaims<T>>::contains_key(claim_identifier),
+				Error::<T>::ClaimAlreadyConsumed
+			);
+			Self::ensure_context_address(account_id, proof)?;
+			Ok(claim_identifier)
+		}
+
+		/// Ensures a proof was generated for `account_id`, i.e. the context address of its claim
+		/// is the hex-encoded account.
+		fn ensure_context_address(
+			account_id: &T::AccountId,
+			proof: &Proof,
+		) -> Result<(), Error<T>> {
+			let context = proof.claimInfo.parse_context().unwrap_or_default();
+			let context_address = hex::decode(context.context_address.trim_start_matches("0x"))
+				.map_err(|_| Error::<T>::ContextAddressMismatch)?;
+			ensure!(context_address == account_id.encode(), Error::<T>::ContextAddressMismatch);
+			Ok(())
+		}
+
+		/// Returns the verification of an account for a provider, unless it has expired or needs
+		/// re-verification
+		pub fn account_verified(
+			account_id: &T::AccountId,
+			provider_hash: &ProviderHash,
+		) -> Option<VerificationRecordOf<T>> {
+			let now = frame_system::Pallet::<T>::block_number();
+			<AccountVerified<T>>::get(account_id, provider_hash)
+				.filter(|record| record.expires_at > now && !Self::relies_on_revoked(record))
+		}
+
+		/// Returns whether an account holds a verification for a provider made with a claim, or
+		/// attested by a witness, revoked since. The account has to verify again with a new
+		/// proof.
+		pub fn needs_reverification(
+			account_id: &T::AccountId,
+			provider_hash: &ProviderHash,
+		) -> bool {
+			<AccountVerified<T>>::get(account_id, provider_hash)
+				.map_or(false, |record| Self::relies_on_revoked(&record))
+		}
+
+		/// Returns whether the claim of a record, or a witness that attested it, has been revoked.
+		/// Every account verified with a revoked claim is covered, not only the one
+		/// `OnClaimRevoked` removes the verification of.
+		fn relies_on_revoked(record: &VerificationRecordOf<T>) -> bool {
+			T::ReclaimVerifier::is_claim_revoked(&record.claim_identifier) ||
+				record.witnesses.iter().any(T::ReclaimVerifier::is_witness_revoked)
+		}
+
+		/// Returns whether an account holds an unexpired verification for a provider
+		pub fn is_verified_for(account_id: &T::AccountId, provider_hash: &ProviderHash) -> bool {
+			Self::account_verified(account_id, provider_hash).is_some()
+		}
+
+		/// Returns whether an account holds an unexpired verification for any provider that needs
+		/// no re-verification
+		pub fn is_verified(account_id: &T::AccountId) -> bool {
+			let now = frame_system::Pallet::<T>::block_number();
+			<AccountVerified<T>>::iter_prefix_values(account_id)
+				.any(|record| record.expires_at > now && !Self::relies_on_revoked(&record))
+		}
+
+		/// Stores a verification that never expires, for benchmarks of gated calls
+		#[cfg(feature = "runtime-benchmarks")]
+		pub(crate) fn insert_benchmark_record(who: &T::AccountId, provider_hash: ProviderHash) {
+			let now = frame_system::Pallet::<T>::block_number();
+			let record = VerificationRecord {
+				verified_at: now,
+				expires_at: BlockNumberFor::<T>::max_value(),
+				epoch: 0,
+				claim_identifier: [0_u8; 32],
+				witnesses: BoundedVec::new(),
+			};
+			<AccountVerified<T>>::insert(who, provider_hash, record);
+		}
+
+		/// Derives the unique identity of a proof from the configured extracted parameter
+		pub fn unique_identity(
+			proof: &Proof,
+			provider_hash: &ProviderHash,
+		) -> Result<Option<T::Hash>, DispatchError> {
+			let Some(parameter) = T::UniqueIdentityParameter::get() else { return Ok(None) };
+			let context = proof.claimInfo.parse_context().unwrap_or_default();
+			let value = context
+				.extracted_parameters
+				.get(parameter)
+				.ok_or(Error::<T>::MissingIdentityParameter)?;
+			Ok(Some(T::Hashing::hash_of(&(provider_hash, value))))
+		}
+
+		/// Removes expired verifications, resuming from where the previous sweep stopped.
+		fn sweep_expired(now: BlockNumberFor<T>, remaining_weight: Weight) -> Weight {
+			let db_weight = T::DbWeight::get();
+			// Reading a verification, and removing it with the relayed claim it was made with
+			let per_item = db_weight.reads_writes(3, 3);
+			let mut used = db_weight.reads_writes(1, 1);
+			if remaining_weight.any_lt(used.saturating_add(per_item)) {
+				return Weight::zero()
+			}
+
+			let mut iter = match <SweepCursor<T>>::get() {
+				Some(cursor) => <AccountVerified<T>>::iter_from(cursor.into_inner()),
+				None => <AccountVerified<T>>::iter(),
+			};
+			let mut expired = Vec::new();
+			let mut exhausted = false;
+			while !remaining_weight.any_lt(used.saturating_add(per_item)) {
+				match iter.next() {
+					Some((account_id, provider_hash, record)) => {
+						used = used.saturating_add(per_item);
+						if record.expires_at <= now {
+							expired.push((account_id, provider_hash, record.claim_identifier));
+						}
+					},
+					None => {
+						exhausted = true;
+						break
+					},
+				}
+			}
+
+			// Storage is only modified once iteration is over
+			let cursor = if exhausted {
+				None
+			} else {
+				BoundedVec::try_from(iter.last_raw_key().to_vec()).ok()
+			};
+			<SweepCursor<T>>::set(cursor);
+			for (account_id, provider_hash, claim_identifier) in expired {
+				<AccountVerified<T>>::remove(&account_id, provider_hash);
+				Self::release_relayed_claim(&claim_identifier, &account_id);
+				Self::deposit_event(Event::VerificationExpired { account_id, provider_hash });
+			}
+			used
+		}
+	}
+}
+
+impl<T: Config> VerificationInspect<T::AccountId> for Pallet<T> {
+	/// Checks the verification of an account for a provider.
+	fn is_verified_for(who: &T::AccountId, provider: &ProviderHash) -> bool {
+		Pallet::<T>::is_verified_for(who, provider)
+	}
+}
+
+impl<T: Config, BlockNumber, Hash, Balance>
+	OnClaimRevoked<VerifiedClaim<T::AccountId, BlockNumber, Hash, Balance>> for Pallet<T>
+{
+	/// Removes the verification made with a revoked claim, of the account it was relayed for or
+	/// else of its submitter. The deposit of the relayer is released.
+	fn on_claim_revoked(
+		identifier: &[u8; 32],
+		claim: &VerifiedClaim<T::AccountId, BlockNumber, Hash, Balance>,
+	) {
+		let account_id = match <RelayedClaims<T>>::take(identifier) {
+			Some(relayed) => {
+				T::Currency::unreserve(&relayed.relayer, relayed.deposit);
+				relayed.account_id
+			},
+			None => claim.submitter.clone(),
+		};
+		let Some(provider_hash) = claim.provider_hash else { return };
+		let made_with_claim = <AccountVerified<T>>::get(&account_id, provider_hash)
+			.map_or(false, |record| record.claim_identifier == *identifier);
+		if made_with_claim {
+			<AccountVerified<T>>::remove(&account_id, provider_hash);
+			Pallet::<T>::deposit_event(Event::VerificationRevoked { account_id, provider_hash });
+		}
+	}
+
+	/// Taking the relayed claim, releasing the deposit of its relayer, and reading and removing
+	/// the verification.
+	fn on_claim_revoked_weight() -> Weight {
+		T::DbWeight::get().reads_writes(3, 3)
+	}
+}
+
+impl<T: Config> ProofCall<Proof> for Call<T> {
+	/// Returns the proof submitted through `verify_user` or `verify_user_for`.
+	fn reclaim_proof(&self) -> Option<Proof> {
+		match self {
+			Call::verify_user { proof } | Call::verify_user_for { proof, .. } =>
+				Some(proof.clone()),
+			_ => None,
+		}
+	}
+}
+
+/// Ensures the origin is signed by an account verified for any provider.
+pub struct EnsureVerified<T>(PhantomData<T>);
+
+impl<T: Config> EnsureOrigin<T::RuntimeOrigin> for EnsureVerified<T> {
+	type Success = T::AccountId;
+
+	fn try_origin(o: T::RuntimeOrigin) -> Result<Self::Success, T::RuntimeOrigin> {
+		o.into().and_then(|o| match o {
+			frame_system::RawOrigin::Signed(who) if Pallet::<T>::is_verified(&who) => Ok(who),
+			r => Err(T::RuntimeOrigin::from(r)),
+		})
+	}
+
+	#[cfg(feature = "runtime-benchmarks")]
+	fn try_successful_origin() -> Result<T::RuntimeOrigin, ()> {
+		let who: T::AccountId = frame_benchmarking::whitelisted_caller();
+		Pallet::<T>::insert_benchmark_record(&who, [0_u8; 32]);
+		Ok(frame_system::RawOrigin::Signed(who).into())
+	}
+}
+
+/// Ensures the origin is signed by an account verified for the provider given by `Provider`.
+pub struct EnsureVerifiedFor<T, Provider>(PhantomData<(T, Provider)>);
+
+impl<T: Config, Provider: Get<ProviderHash>> EnsureOrigin<T::RuntimeOrigin>
+	for EnsureVerifiedFor<T, Provider>
+{
+	type Success = T::AccountId;
+
+	fn try_origin(o: T::RuntimeOrigin) -> Result<Self::Success, T::RuntimeOrigin> {
+		o.into().and_then(|o| match o {
+			frame_system::RawOrigin::Signed(who)
+				if Pallet::<T>::is_verified_for(&who, &Provider::get()) =>
+				Ok(who),
+			r => Err(T::RuntimeOrigin::from(r)),
+		})
+	}
+
+	#[cfg(feature = "runtime-benchmarks")]
+	fn try_successful_origin() -> Result<T::RuntimeOrigin, ()> {
+		let who: T::AccountId = frame_benchmarking::whitelisted_caller();
+		Pallet::<T>::insert_benchmark_record(&who, Provider::get());
+		Ok(frame_system::RawOrigin::Signed(who).into())
 	}
 }
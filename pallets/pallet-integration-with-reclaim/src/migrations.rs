@@ -0,0 +1,278 @@
+//! Storage migrations of pallet-integration-with-reclaim.
+//!
+//! Each `vN` module holds the migration to storage version `N`. Migrations check the on-chain
+//! storage version and do nothing once it has been reached.
+use crate::{AccountVerified, Config, Pallet, VerificationRecord};
+use codec::{Decode, Encode};
+use core::marker::PhantomData;
+use frame_support::{
+	sp_runtime::traits::Saturating,
+	traits::{Get, GetStorageVersion, OnRuntimeUpgrade, StorageVersion},
+	weights::Weight,
+	BoundedVec,
+};
+use frame_system::pallet_prelude::BlockNumberFor;
+use pallet_reclaim::ProviderHash;
+use scale_info::prelude::vec::Vec;
+#[cfg(feature = "try-runtime")]
+use frame_support::ensure;
+#[cfg(feature = "try-runtime")]
+use frame_support::sp_runtime::TryRuntimeError;
+
+/// Storage layout of the unversioned pallet.
+pub mod v0 {
+	use super::*;
+	use frame_support::{pallet_prelude::OptionQuery, storage_alias, Identity};
+
+	/// Verified accounts, without expiry.
+	#[storage_alias]
+	pub type AccountVerified<T: Config> = StorageMap<
+		Pallet<T>,
+		Identity,
+		<T as frame_system::Config>::AccountId,
+		bool,
+		OptionQuery,
+	>;
+}
+
+/// Migration to storage version 1.
+pub mod v1 {
+	use super::*;
+	use frame_support::{pallet_prelude::OptionQuery, storage_alias, Identity};
+
+	/// Verifications of storage version 1, one per account.
+	#[storage_alias]
+	pub type AccountVerified<T: Config> = StorageMap<
+		Pallet<T>,
+		Identity,
+		<T as frame_system::Config>::AccountId,
+		v3::OldVerificationRecord<BlockNumberFor<T>>,
+		OptionQuery,
+	>;
+
+	/// Moves each account of the unversioned `AccountVerified` map into a verification record
+	/// expiring `VerificationTtl` blocks after the upgrade.
+	///
+	/// The claim of these verifications is unknown, so their records hold epoch 0 and an
+	/// all-zero claim identifier.
+	pub struct MigrateToV1<T>(PhantomData<T>);
+
+	impl<T: Config> OnRuntimeUpgrade for MigrateToV1<T> {
+		fn on_runtime_upgrade() -> Weight {
+			let on_chain_version = Pallet::<T>::on_chain_storage_version();
+			if on_chain_version != 0 {
+				log::info!(
+					target: "runtime::integration-with-reclaim",
+					"Skipping migration to v1, storage is at {:?}",
+					on_chain_version
+				);
+				return T::DbWeight::get().reads(1)
+			}
+
+			let now = frame_system::Pallet::<T>::block_number();
+			let expires_at = now.saturating_add(T::VerificationTtl::get());
+			// Old entries are drained before any record is written under the same prefix
+			let verified = v0::AccountVerified::<T>::drain().collect::<Vec<_>>();
+			let drained = verified.len() as u64;
+			let mut migrated = 0_u64;
+			for (account_id, _) in verified.into_iter().filter(|(_, verified)| *verified) {
+				let record = v3::OldVerificationRecord {
+					verified_at: now,
+					expires_at,
+					epoch: 0,
+					claim_identifier: [0_u8; 32],
+				};
+				AccountVerified::<T>::insert(account_id, record);
+				migrated += 1;
+			}
+			StorageVersion::new(1).put::<Pallet<T>>();
+			log::info!(
+				target: "runtime::integration-with-reclaim",
+				"Migrated {} verifications to v1",
+				migrated
+			);
+			T::DbWeight::get().reads_writes(drained + 1, drained + migrated + 1)
+		}
+
+		#[cfg(feature = "try-runtime")]
+		fn pre_upgrade() -> Result<Vec<u8>, TryRuntimeError> {
+			let verifications = if Pallet::<T>::on_chain_storage_version() == 0 {
+				v0::AccountVerified::<T>::iter_values().filter(|verified| *verified).count()
+			} else {
+				AccountVerified::<T>::iter_values().count()
+			};
+			Ok((verifications as u32).encode())
+		}
+
+		#[cfg(feature = "try-runtime")]
+		fn post_upgrade(state: Vec<u8>) -> Result<(), TryRuntimeError> {
+			let verifications = u32::decode(&mut &state[..])
+				.map_err(|_| "Failed to decode the verification count of pre_upgrade")?;
+			ensure!(
+				Pallet::<T>::on_chain_storage_version() >= 1,
+				"Storage version was not updated to v1"
+			);
+			ensure!(
+				AccountVerified::<T>::iter_values().count() as u32 == verifications,
+				"Verifications were lost or no longer decode"
+			);
+			Ok(())
+		}
+	}
+}
+
+/// Migration to storage version 2.
+pub mod v2 {
+	use super::*;
+	use frame_support::{pallet_prelude::OptionQuery, storage_alias, Identity};
+
+	/// Verifications of storage version 2, one per account and provider.
+	#[storage_alias]
+	pub type AccountVerified<T: Config> = StorageDoubleMap<
+		Pallet<T>,
+		Identity,
+		<T as frame_system::Config>::AccountId,
+		Identity,
+		ProviderHash,
+		v3::OldVerificationRecord<BlockNumberFor<T>>,
+		OptionQuery,
+	>;
+
+	/// Provider hash the verifications of storage version 1 are recorded under, as they were
+	/// not made for a known provider.
+	pub const LEGACY_PROVIDER_HASH: ProviderHash = [0_u8; 32];
+
+	/// Moves each verification of storage version 1 under `LEGACY_PROVIDER_HASH`.
+	///
+	/// These verifications are not bound to a provider a claim can declare.
+	pub struct MigrateToV2<T>(PhantomData<T>);
+
+	impl<T: Config> OnRuntimeUpgrade for MigrateToV2<T> {
+		fn on_runtime_upgrade() -> Weight {
+			let on_chain_version = Pallet::<T>::on_chain_storage_version();
+			if on_chain_version != 1 {
+				log::info!(
+					target: "runtime::integration-with-reclaim",
+					"Skipping migration to v2, storage is at {:?}",
+					on_chain_version
+				);
+				return T::DbWeight::get().reads(1)
+			}
+
+			// Old entries are drained before any record is written under the same prefix
+			let verified = v1::AccountVerified::<T>::drain().collect::<Vec<_>>();
+			let migrated = verified.len() as u64;
+			for (account_id, record) in verified {
+				AccountVerified::<T>::insert(account_id, LEGACY_PROVIDER_HASH, record);
+			}
+			StorageVersion::new(2).put::<Pallet<T>>();
+			log::info!(
+				target: "runtime::integration-with-reclaim",
+				"Migrated {} verifications to v2",
+				migrated
+			);
+			T::DbWeight::get().reads_writes(migrated + 1, 2 * migrated + 1)
+		}
+
+		#[cfg(feature = "try-runtime")]
+		fn pre_upgrade() -> Result<Vec<u8>, TryRuntimeError> {
+			let verifications = if Pallet::<T>::on_chain_storage_version() == 1 {
+				v1::AccountVerified::<T>::iter_values().count()
+			} else {
+				AccountVerified::<T>::iter_values().count()
+			};
+			Ok((verifications as u32).encode())
+		}
+
+		#[cfg(feature = "try-runtime")]
+		fn post_upgrade(state: Vec<u8>) -> Result<(), TryRuntimeError> {
+			let verifications = u32::decode(&mut &state[..])
+				.map_err(|_| "Failed to decode the verification count of pre_upgrade")?;
+			ensure!(
+				Pallet::<T>::on_chain_storage_version() >= 2,
+				"Storage version was not updated to v2"
+			);
+			ensure!(
+				AccountVerified::<T>::iter_values().count() as u32 == verifications,
+				"Verifications were lost or no longer decode"
+			);
+			Ok(())
+		}
+	}
+}
+
+/// Migration to storage version 3.
+pub mod v3 {
+	use super::*;
+
+	/// Layout of `VerificationRecord` up to storage version 2, without witnesses.
+	#[derive(Encode, Decode, Clone, PartialEq, Eq, Debug)]
+	pub struct OldVerificationRecord<BlockNumber> {
+		pub verified_at: BlockNumber,   // Block in which the account was verified
+		pub expires_at: BlockNumber,    // Block from which the verification is no longer valid
+		pub epoch: u64,                 // Epoch the verified claim was made in
+		pub claim_identifier: [u8; 32], // Identifier of the verified claim
+	}
+
+	/// Adds the attesting witnesses to every verification record, empty as they are unknown.
+	///
+	/// Revoking a witness therefore does not affect the records migrated.
+	pub struct MigrateToV3<T>(PhantomData<T>);
+
+	impl<T: Config> OnRuntimeUpgrade for MigrateToV3<T> {
+		fn on_runtime_upgrade() -> Weight {
+			let on_chain_version = Pallet::<T>::on_chain_storage_version();
+			if on_chain_version != 2 {
+				log::info!(
+					target: "runtime::integration-with-reclaim",
+					"Skipping migration to v3, storage is at {:?}",
+					on_chain_version
+				);
+				return T::DbWeight::get().reads(1)
+			}
+
+			let mut translated = 0_u64;
+			AccountVerified::<T>::translate::<OldVerificationRecord<BlockNumberFor<T>>, _>(
+				|_, _, old| {
+					translated += 1;
+					Some(VerificationRecord {
+						verified_at: old.verified_at,
+						expires_at: old.expires_at,
+						epoch: old.epoch,
+						claim_identifier: old.claim_identifier,
+						witnesses: BoundedVec::new(),
+					})
+				},
+			);
+			StorageVersion::new(3).put::<Pallet<T>>();
+			log::info!(
+				target: "runtime::integration-with-reclaim",
+				"Migrated {} verifications to v3",
+				translated
+			);
+			T::DbWeight::get().reads_writes(translated + 1, translated + 1)
+		}
+
+		#[cfg(feature = "try-runtime")]
+		fn pre_upgrade() -> Result<Vec<u8>, TryRuntimeError> {
+			let verifications = AccountVerified::<T>::iter_keys().count() as u32;
+			Ok(verifications.encode())
+		}
+
+		#[cfg(feature = "try-runtime")]
+		fn post_upgrade(state: Vec<u8>) -> Result<(), TryRuntimeError> {
+			let verifications = u32::decode(&mut &state[..])
+				.map_err(|_| "Failed to decode the verification count of pre_upgrade")?;
+			ensure!(
+				Pallet::<T>::on_chain_storage_version() >= 3,
+				"Storage version was not updated to v3"
+			);
+			// Records that no longer decode are skipped by `iter_values`
+			ensure!(
+				AccountVerified::<T>::iter_values().count() as u32 == verifications,
+				"Verifications were lost or no longer decode"
+			);
+			Ok(())
+		}
+	}
+}
@@ -1,9 +1,14 @@
 use crate as pallet_integration_with_reclaim;
-use frame_support::traits::{ConstU16, ConstU64};
+use frame_support::{
+	parameter_types,
+	traits::{ConstU16, ConstU64},
+	weights::{constants::WEIGHT_REF_TIME_PER_SECOND, Weight},
+};
 use sp_core::H256;
 use sp_runtime::{
-	traits::{BlakeTwo256, IdentityLookup},
-	BuildStorage,
+	testing::{TestSignature, TestXt, UintAuthorityId},
+	traits::{BlakeTwo256, Extrinsic as ExtrinsicT, IdentityLookup},
+	BuildStorage, Perbill,
 };
 
 type Block = frame_system::mocking::MockBlock<Test>;
@@ -14,14 +19,25 @@ frame_support::construct_runtime!(
 	{
 		System: frame_system,
 		Timestamp: pallet_timestamp,
+		Balances: pallet_balances,
 		Reclaim: pallet_reclaim,
 		IntegrationWithReclaim: pallet_integration_with_reclaim,
 	}
 );
 
+parameter_types! {
+	// Same limits as the node template runtime, so the weights of the pallets are checked
+	// against them.
+	pub BlockWeights: frame_system::limits::BlockWeights =
+		frame_system::limits::BlockWeights::with_sensible_defaults(
+			Weight::from_parts(2u64 * WEIGHT_REF_TIME_PER_SECOND, u64::MAX),
+			Perbill::from_percent(75),
+		);
+}
+
 impl frame_system::Config for Test {
 	type BaseCallFilter = frame_support::traits::Everything;
-	type BlockWeights = ();
+	type BlockWeights = BlockWeights;
 	type BlockLength = ();
 	type DbWeight = ();
 	type RuntimeOrigin = RuntimeOrigin;
@@ -36,7 +52,7 @@ impl frame_system::Config for Test {
 	type BlockHashCount = ConstU64<250>;
 	type Version = ();
 	type PalletInfo = PalletInfo;
-	type AccountData = ();
+	type AccountData = pallet_balances::AccountData<u64>;
 	type OnNewAccount = ();
 	type OnKilledAccount = ();
 	type SystemWeightInfo = ();
@@ -52,17 +68,110 @@ impl pallet_timestamp::Config for Test {
 	type WeightInfo = ();
 }
 
+impl pallet_balances::Config for Test {
+	type MaxLocks = ();
+	type MaxReserves = ();
+	type ReserveIdentifier = [u8; 8];
+	type Balance = u64;
+	type RuntimeEvent = RuntimeEvent;
+	type DustRemoval = ();
+	type ExistentialDeposit = ConstU64<1>;
+	type AccountStore = System;
+	type WeightInfo = ();
+	type FreezeIdentifier = ();
+	type MaxFreezes = ();
+	type RuntimeHoldReason = ();
+	type MaxHolds = ();
+}
+
+pub type Extrinsic = TestXt<RuntimeCall, ()>;
+
+impl frame_system::offchain::SigningTypes for Test {
+	type Public = UintAuthorityId;
+	type Signature = TestSignature;
+}
+
+impl<LocalCall> frame_system::offchain::SendTransactionTypes<LocalCall> for Test
+where
+	RuntimeCall: From<LocalCall>,
+{
+	type OverarchingCall = RuntimeCall;
+	type Extrinsic = Extrinsic;
+}
+
+impl<LocalCall> frame_system::offchain::CreateSignedTransaction<LocalCall> for Test
+where
+	RuntimeCall: From<LocalCall>,
+{
+	fn create_transaction<C: frame_system::offchain::AppCrypto<UintAuthorityId, TestSignature>>(
+		call: RuntimeCall,
+		_public: UintAuthorityId,
+		account: u64,
+		_nonce: u64,
+	) -> Option<(RuntimeCall, <Extrinsic as ExtrinsicT>::SignaturePayload)> {
+		Some((call, (account, ())))
+	}
+}
+
+pub struct TestAuthId;
+
+impl frame_system::offchain::AppCrypto<UintAuthorityId, TestSignature> for TestAuthId {
+	type RuntimeAppPublic = UintAuthorityId;
+	type GenericPublic = UintAuthorityId;
+	type GenericSignature = TestSignature;
+}
+
+parameter_types! {
+	pub const EpochSourceUrl: &'static str = "http://localhost:8001/epoch";
+	pub EpochSourceSigners: Vec<[u8; 20]> = Vec::new();
+	pub static UniqueIdentityParameter: Option<&'static str> = Some("CLAIM_DATA");
+	pub static VerifiedClaimDeposit: Option<u64> = None;
+	pub static RelayedClaimDeposit: u64 = 0;
+	pub static ProofAttemptWindow: u64 = 0;
+	pub static MaxProofAttempts: u32 = 0;
+}
+
 impl pallet_reclaim::Config for Test {
 	type RuntimeEvent = RuntimeEvent;
 	type Signature = sp_core::ecdsa::Signature;
 	type PublicKey = sp_core::ecdsa::Public;
-	type WeightInfo = ();
+	type WitnessSignatureScheme = pallet_reclaim::schemes::EthereumWitnesses;
+	type AuthorityId = TestAuthId;
+	type EpochSourceUrl = EpochSourceUrl;
+	type EpochSourceSigners = EpochSourceSigners;
+	type EpochSyncInterval = ConstU64<10>;
+	type MaxBatch = frame_support::traits::ConstU32<10>;
+	type InitOrigin = frame_system::EnsureRoot<u64>;
+	type AdminOrigin = frame_system::EnsureRoot<u64>;
+	type ProofAttemptWindow = ProofAttemptWindow;
+	type MaxProofAttempts = MaxProofAttempts;
+	type Currency = Balances;
+	type VerifiedClaimDeposit = VerifiedClaimDeposit;
+	type OnProofVerified = ();
+	type OnClaimRevoked = IntegrationWithReclaim;
+	type OptimisticBond = ();
+	type OptimisticBondPerByte = ConstU64<0>;
+	type ChallengePeriod = ConstU64<1>;
+	type MaxPendingPerBlock = frame_support::traits::ConstU32<0>;
+	type MaxOptimisticProofSize = frame_support::traits::ConstU32<0>;
+	type Slash = ();
+	type ChallengerReward = ();
+	type WeightInfo = pallet_reclaim::weights::SubstrateWeightInfo<Test>;
 }
 
 impl pallet_integration_with_reclaim::Config for Test {
 	type RuntimeEvent = RuntimeEvent;
 	type ReclaimVerifier = pallet_reclaim::Pallet<Test>;
-	type WeightInfo = ();
+	type VerificationTtl = ConstU64<100>;
+	type UniqueIdentityParameter = UniqueIdentityParameter;
+	type IdentityAdminOrigin = frame_system::EnsureRoot<u64>;
+	type UnsignedPriority = ConstU64<100>;
+	type UnsignedLongevity = ConstU64<64>;
+	type AuthorizationSignature = TestSignature;
+	type AuthorizationSigner = UintAuthorityId;
+	type Currency = Balances;
+	type RelayedClaimDeposit = RelayedClaimDeposit;
+	type WeightInfo = crate::weights::SubstrateWeightInfo<Test>;
 }
 
 // Build genesis storage according to the mock runtime.
@@ -1,73 +1,716 @@
 #![allow(non_snake_case)]
 use super::*;
 use crate::mock::*;
-use frame_support::assert_ok;
+use codec::Encode;
+use frame_support::{
+	assert_err, assert_ok,
+	dispatch::GetDispatchInfo,
+	parameter_types,
+	traits::{
+		Currency, EnsureOrigin, Get, GetStorageVersion, Hooks, OnRuntimeUpgrade,
+		ReservableCurrency, StorageVersion,
+	},
+	unsigned::ValidateUnsigned,
+	weights::Weight,
+};
 use frame_system::RawOrigin;
-use pallet_reclaim::{ClaimInfo, CompleteClaimData, SignedClaim, Witness};
-use sp_core::ConstU32;
-use sp_runtime::BoundedVec;
+use pallet_reclaim::{proof_builder, ClaimInfo, CompleteClaimData, SignedClaim, Witness};
+use sp_core::{ConstU32, H256};
+use sp_runtime::{
+	testing::TestSignature,
+	transaction_validity::{InvalidTransaction, TransactionSource},
+	BoundedVec,
+};
+
+type ReclaimWeights = pallet_reclaim::weights::SubstrateWeightInfo<Test>;
+type IntegrationWeights = weights::SubstrateWeightInfo<Test>;
+
+// Provider hash declared in the context of the proofs returned by `setup_proof` and
+// `setup_steam_proof`.
+fn provider_hash() -> ProviderHash {
+	hex::decode(&proof_builder::PROVIDER_HASH[2..]).unwrap().try_into().unwrap()
+}
+
+// Initializes Reclaim with generated witnesses and returns a proof generated for `account_id`.
+fn setup_proof(account_id: u64) -> Proof {
+	assert_ok!(Reclaim::init(RawOrigin::Root.into(), 1));
+	assert_ok!(Reclaim::add_epoch(RawOrigin::Signed(1).into(), proof_builder::witnesses(5), 2));
+	proof_for(account_id, "76561199601812329")
+}
+
+// Returns a proof of the identity `claim_data` generated for `account_id`, valid in epoch 1.
+fn proof_for(account_id: u64, claim_data: &str) -> Proof {
+	let context_address = format!("0x{}", hex::encode(account_id.encode()));
+	let claim_info = ClaimInfo {
+		provider: "http".to_string(),
+		parameters: "{}".to_string(),
+		context: proof_builder::claim_context(&context_address, claim_data),
+	};
+	proof_builder::build_proof(&Reclaim::epochs(1).unwrap(), claim_info, 1712174155)
+}
+
+// Initializes Reclaim with the account `owner`, adds the epoch of the Reclaim witness and returns
+// a proof of a Steam ID it signed. Only kept to check real proofs still verify.
+fn setup_steam_proof(owner: u64) -> Proof {
+	assert_ok!(Reclaim::init(RawOrigin::Root.into(), owner));
+	let addr = hex::decode("244897572368eadf65bfbc5aec98d8e5443a9072").unwrap();
+	let addr_slice = addr.get(0..20).unwrap();
+
+	let mut addr_normzlized: [u8; 20] = [0_u8; 20];
+
+	for i in 0..20 {
+		addr_normzlized[i] = addr_slice[i]
+	}
+
+	let w1 = Witness { address: addr_normzlized, host: [1_u8; 32] };
+
+	let mut witnesses_vec = Vec::<Witness>::new();
+	witnesses_vec.push(w1);
+	let witnesses: BoundedVec<Witness, ConstU32<100>> =
+		BoundedVec::<Witness, ConstU32<100>>::try_from(witnesses_vec).unwrap();
+	let minimum_witness = 1;
+	assert_ok!(Reclaim::add_epoch(RawOrigin::Signed(owner).into(), witnesses, minimum_witness));
+	let claim_info = ClaimInfo {
+		provider: "http".to_string(),
+		parameters: "{\"body\":\"\",\"geoLocation\":\"in\",\"method\":\"GET\",\"responseMatches\":[{\"type\":\"regex\",\"value\":\"_steamid\\\">Steam ID: (?<CLAIM_DATA>.*)</div>\"}],\"responseRedactions\":[{\"jsonPath\":\"\",\"regex\":\"_steamid\\\">Steam ID: (?<CLAIM_DATA>.*)</div>\",\"xPath\":\"id(\\\"responsive_page_template_content\\\")/div[@class=\\\"page_header_ctn\\\"]/div[@class=\\\"page_content\\\"]/div[@class=\\\"youraccount_steamid\\\"]\"}],\"url\":\"https://store.steampowered.com/account/\"}".to_string(),
+		context: "{\"contextAddress\":\"user's address\",\"contextMessage\":\"for acmecorp.com on 1st january\",\"extractedParameters\":{\"CLAIM_DATA\":\"76561199601812329\"},\"providerHash\":\"0xffd5f761e0fb207368d9ebf9689f077352ab5d20ae0a2c23584c2cd90fc1b1bf\"}".to_string(),
+	};
+	let complete_claim_data = CompleteClaimData {
+		identifier: "0xd1dcfc5338cb588396e44e6449e8c750bd4d76332c7e9440c92383382fced0fd"
+			.to_string(),
+		owner: "0x13239fc6bf3847dfedaf067968141ec0363ca42f".to_string(),
+		epoch: 1_u64,
+		timestampS: 1712174155_u64,
+	};
+
+	let mut sigs = Vec::<String>::new();
+
+	let str_signature = "2888485f650f8ed02d18e32dd9a1512ca05feb83fc2cbf2df72fd8aa4246c5ee541fa53875c70eb64d3de9143446229a250c7a762202b7cc289ed31b74b31c811c".to_string();
+
+	sigs.push(str_signature);
+
+	let signed_claim = SignedClaim { claim: complete_claim_data, signatures: sigs };
+
+	Proof { claimInfo: claim_info, signedClaim: signed_claim }
+}
 
 #[test]
 fn should_verify_user() {
 	new_test_ext().execute_with(|| {
 		let source_account_id = 1;
 		System::set_block_number(1);
-		assert_ok!(Reclaim::init(RawOrigin::Signed(source_account_id).into()));
-		let addr = hex::decode("244897572368eadf65bfbc5aec98d8e5443a9072").unwrap();
-		let addr_slice = addr.get(0..20).unwrap();
+		let proof = setup_steam_proof(source_account_id);
+		// The real proof was generated for no account of this chain, so it can't bind an identity
+		UniqueIdentityParameter::set(None);
+
+		assert_ok!(IntegrationWithReclaim::verify_user(
+			RawOrigin::Signed(source_account_id).into(),
+			proof
+		));
+
+		let identifier =
+			hex::decode("d1dcfc5338cb588396e44e6449e8c750bd4d76332c7e9440c92383382fced0fd")
+				.unwrap();
+		System::assert_has_event(
+			Event::UserVerified { account_id: source_account_id, provider_hash: provider_hash() }
+				.into(),
+		);
+		assert!(IntegrationWithReclaim::is_verified_for(&source_account_id, &provider_hash()));
+		assert!(!IntegrationWithReclaim::is_verified_for(&source_account_id, &[0_u8; 32]));
+		assert_eq!(
+			IntegrationWithReclaim::account_verified(&source_account_id, &provider_hash()),
+			Some(VerificationRecord {
+				verified_at: 1,
+				expires_at: 101,
+				epoch: 1,
+				claim_identifier: identifier.try_into().unwrap(),
+				witnesses: BoundedVec::truncate_from(vec![
+					hex::decode("244897572368eadf65bfbc5aec98d8e5443a9072")
+						.unwrap()
+						.try_into()
+						.unwrap()
+				]),
+			})
+		);
+	})
+}
+
+#[test]
+fn should_include_proof_verification_in_weight() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		let proof = setup_proof(5);
+		let signatures = proof.signedClaim.signatures.len() as u32;
+		let verify_proof = |witnesses| {
+			<ReclaimWeights as pallet_reclaim::WeightInfo>::verify_proof(
+				signatures,
+				witnesses,
+				proof.claimInfo.size(),
+			)
+			.saturating_add(<IntegrationWeights as weights::WeightInfo>::verify_user())
+		};
+		let call = Call::<Test>::verify_user { proof: proof.clone() };
+		assert_eq!(call.get_dispatch_info().weight, verify_proof(pallet_reclaim::MAX_WITNESSES));
 
-		let mut addr_normzlized: [u8; 20] = [0_u8; 20];
+		// Only the witnesses of the current epoch are charged
+		let post_info =
+			IntegrationWithReclaim::verify_user(RawOrigin::Signed(5).into(), proof.clone())
+				.unwrap();
+		assert_eq!(post_info.actual_weight, Some(verify_proof(5)));
+	})
+}
 
-		for i in 0..20 {
-			addr_normzlized[i] = addr_slice[i]
+#[test]
+fn should_fit_verifications_in_a_block() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		let mut proof = setup_proof(5);
+		proof.signedClaim.signatures.truncate(1);
+		let max_block = <Test as frame_system::Config>::BlockWeights::get().max_block;
+		let calls = [
+			Call::<Test>::verify_user { proof: proof.clone() },
+			Call::<Test>::verify_user_unsigned { account_id: 5, proof: proof.clone() },
+			Call::<Test>::verify_user_for {
+				target: 5,
+				proof,
+				authorization: TestSignature(5, Vec::new()),
+			},
+		];
+		for call in calls {
+			assert!(call.get_dispatch_info().weight.all_lt(max_block));
 		}
+	})
+}
 
-		let w1 = Witness { address: addr_normzlized, host: [1_u8; 32] };
+#[test]
+fn should_migrate_verifications_to_v1() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(10);
+		StorageVersion::new(0).put::<IntegrationWithReclaim>();
+		migrations::v0::AccountVerified::<Test>::insert(5, true);
+		migrations::v0::AccountVerified::<Test>::insert(6, false);
+
+		migrations::v1::MigrateToV1::<Test>::on_runtime_upgrade();
+		assert_eq!(IntegrationWithReclaim::on_chain_storage_version(), 1);
+		assert_eq!(
+			migrations::v1::AccountVerified::<Test>::get(5),
+			Some(migrations::v3::OldVerificationRecord {
+				verified_at: 10,
+				expires_at: 110,
+				epoch: 0,
+				claim_identifier: [0_u8; 32],
+			})
+		);
+		assert!(!migrations::v1::AccountVerified::<Test>::contains_key(6));
 
-		let mut witnesses_vec = Vec::<Witness>::new();
-		witnesses_vec.push(w1);
-		let witnesses: BoundedVec<Witness, ConstU32<100>> =
-			BoundedVec::<Witness, ConstU32<100>>::try_from(witnesses_vec).unwrap();
-		let minimum_witness = 1;
-		assert_ok!(Reclaim::add_epoch(
+		// The migration only runs once
+		migrations::v1::MigrateToV1::<Test>::on_runtime_upgrade();
+		assert_eq!(migrations::v1::AccountVerified::<Test>::iter().count(), 1);
+	})
+}
+
+#[test]
+fn should_migrate_baseline_encoded_verifications() {
+	new_test_ext().execute_with(|| {
+		StorageVersion::new(0).put::<IntegrationWithReclaim>();
+		// `AccountVerified` of the baseline: an `Identity` map from the account to `true`
+		let mut key = frame_support::storage::storage_prefix(
+			b"IntegrationWithReclaim",
+			b"AccountVerified",
+		)
+		.to_vec();
+		key.extend(5_u64.encode());
+		frame_support::storage::unhashed::put_raw(&key, &[1]);
+
+		migrations::v1::MigrateToV1::<Test>::on_runtime_upgrade();
+		migrations::v2::MigrateToV2::<Test>::on_runtime_upgrade();
+		migrations::v3::MigrateToV3::<Test>::on_runtime_upgrade();
+		assert_eq!(frame_support::storage::unhashed::get_raw(&key), None);
+		assert_eq!(IntegrationWithReclaim::on_chain_storage_version(), 3);
+		assert!(IntegrationWithReclaim::is_verified(&5));
+	})
+}
+
+#[test]
+fn should_migrate_verifications_to_v2() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(10);
+		StorageVersion::new(1).put::<IntegrationWithReclaim>();
+		let record = migrations::v3::OldVerificationRecord {
+			verified_at: 1,
+			expires_at: 101,
+			epoch: 1,
+			claim_identifier: [1_u8; 32],
+		};
+		migrations::v1::AccountVerified::<Test>::insert(5, record.clone());
+
+		migrations::v2::MigrateToV2::<Test>::on_runtime_upgrade();
+		assert_eq!(IntegrationWithReclaim::on_chain_storage_version(), 2);
+		assert_eq!(
+			migrations::v2::AccountVerified::<Test>::get(5, migrations::v2::LEGACY_PROVIDER_HASH),
+			Some(record)
+		);
+
+		// The migration only runs once
+		migrations::v2::MigrateToV2::<Test>::on_runtime_upgrade();
+		assert_eq!(migrations::v2::AccountVerified::<Test>::iter_prefix(5).count(), 1);
+	})
+}
+
+#[test]
+fn should_migrate_verifications_to_v3() {
+	new_test_ext().execute_with(|| {
+		StorageVersion::new(2).put::<IntegrationWithReclaim>();
+		let legacy = migrations::v2::LEGACY_PROVIDER_HASH;
+		migrations::v2::AccountVerified::<Test>::insert(
+			5,
+			legacy,
+			migrations::v3::OldVerificationRecord {
+				verified_at: 1_u64,
+				expires_at: 101,
+				epoch: 0,
+				claim_identifier: [0_u8; 32],
+			},
+		);
+
+		migrations::v3::MigrateToV3::<Test>::on_runtime_upgrade();
+		assert_eq!(IntegrationWithReclaim::on_chain_storage_version(), 3);
+		assert_eq!(
+			pallet::AccountVerified::<Test>::get(5, legacy),
+			Some(VerificationRecord {
+				verified_at: 1,
+				expires_at: 101,
+				epoch: 0,
+				claim_identifier: [0_u8; 32],
+				witnesses: BoundedVec::new(),
+			})
+		);
+
+		// Legacy verifications are not bound to a known provider
+		assert!(IntegrationWithReclaim::is_verified(&5));
+		assert!(!IntegrationWithReclaim::is_verified_for(&5, &provider_hash()));
+	})
+}
+
+#[test]
+fn should_expire_verification() {
+	new_test_ext().execute_with(|| {
+		let source_account_id = 1;
+		System::set_block_number(1);
+		let proof = setup_proof(source_account_id);
+		assert_ok!(IntegrationWithReclaim::verify_user(
 			RawOrigin::Signed(source_account_id).into(),
-			witnesses,
-			minimum_witness
+			proof
+		));
+
+		System::set_block_number(101);
+		assert_eq!(
+			IntegrationWithReclaim::account_verified(&source_account_id, &provider_hash()),
+			None
+		);
+
+		IntegrationWithReclaim::on_idle(101, Weight::MAX);
+		System::assert_has_event(
+			Event::VerificationExpired {
+				account_id: source_account_id,
+				provider_hash: provider_hash(),
+			}
+			.into(),
+		);
+		assert!(!pallet::AccountVerified::<Test>::contains_key(
+			source_account_id,
+			provider_hash()
+		));
+	})
+}
+
+#[test]
+fn should_bind_identity_to_one_account() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		let proof = setup_proof(1);
+
+		// A copy of the proof can't bind the identity to another account
+		assert_err!(
+			IntegrationWithReclaim::verify_user(RawOrigin::Signed(2).into(), proof.clone()),
+			Error::<Test>::ContextAddressMismatch
+		);
+		let identity =
+			IntegrationWithReclaim::unique_identity(&proof, &provider_hash()).unwrap().unwrap();
+		assert_eq!(IntegrationWithReclaim::identity_owner(identity), None);
+		assert_ok!(IntegrationWithReclaim::verify_user(RawOrigin::Signed(1).into(), proof.clone()));
+
+		// The same Steam account can't verify a second account
+		let proof_for_2 = proof_for(2, "76561199601812329");
+		assert_err!(
+			IntegrationWithReclaim::verify_user(RawOrigin::Signed(2).into(), proof_for_2.clone()),
+			Error::<Test>::IdentityAlreadyClaimed
+		);
+
+		// Until governance reassigns the identity, which unverifies the previous account
+		assert_eq!(IntegrationWithReclaim::identity_owner(identity), Some(1));
+		assert_err!(
+			IntegrationWithReclaim::reassign_identity(RawOrigin::Root.into(), H256::zero(), 2),
+			Error::<Test>::UnknownIdentity
+		);
+		assert_ok!(IntegrationWithReclaim::reassign_identity(RawOrigin::Root.into(), identity, 2));
+		System::assert_has_event(
+			Event::VerificationRevoked { account_id: 1, provider_hash: provider_hash() }.into(),
+		);
+		assert!(!IntegrationWithReclaim::is_verified_for(&1, &provider_hash()));
+		assert_ok!(IntegrationWithReclaim::verify_user(RawOrigin::Signed(2).into(), proof_for_2));
+		assert!(IntegrationWithReclaim::is_verified_for(&2, &provider_hash()));
+	})
+}
+
+parameter_types! {
+	pub SteamProvider: ProviderHash = provider_hash();
+	pub OtherProvider: ProviderHash = [0_u8; 32];
+}
+
+#[test]
+fn should_ensure_verified_origin() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		let proof = setup_proof(1);
+		assert!(EnsureVerified::<Test>::try_origin(RawOrigin::Signed(1).into()).is_err());
+		assert_ok!(IntegrationWithReclaim::verify_user(RawOrigin::Signed(1).into(), proof));
+
+		assert_eq!(EnsureVerified::<Test>::try_origin(RawOrigin::Signed(1).into()).ok(), Some(1));
+		assert!(EnsureVerified::<Test>::try_origin(RawOrigin::Signed(2).into()).is_err());
+		assert!(EnsureVerified::<Test>::try_origin(RawOrigin::Root.into()).is_err());
+		assert_eq!(
+			EnsureVerifiedFor::<Test, SteamProvider>::try_origin(RawOrigin::Signed(1).into()).ok(),
+			Some(1)
+		);
+		assert!(
+			EnsureVerifiedFor::<Test, OtherProvider>::try_origin(RawOrigin::Signed(1).into())
+				.is_err()
+		);
+
+		// Expired verifications no longer pass
+		System::set_block_number(101);
+		assert!(EnsureVerified::<Test>::try_origin(RawOrigin::Signed(1).into()).is_err());
+	})
+}
+
+#[test]
+fn should_verify_user_unsigned() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		let proof = setup_proof(5);
+		let validate = |account_id: u64, proof: &Proof| {
+			let call = Call::verify_user_unsigned { account_id, proof: proof.clone() };
+			IntegrationWithReclaim::validate_unsigned(TransactionSource::External, &call)
+		};
+
+		// Proofs are only valid for the account they were generated for
+		assert_eq!(validate(6, &proof), Err(InvalidTransaction::BadSigner.into()));
+		let mut forged = proof.clone();
+		forged.signedClaim.signatures[0] = proof_builder::sign_claim(
+			&forged.signedClaim.claim,
+			&proof_builder::witness_key(99),
+		);
+		assert_eq!(validate(5, &forged), Err(InvalidTransaction::BadProof.into()));
+
+		let valid = validate(5, &proof).unwrap();
+		assert_eq!(valid.priority, 100);
+		assert_eq!(valid.longevity, 64);
+		assert_ok!(IntegrationWithReclaim::verify_user_unsigned(
+			RawOrigin::None.into(),
+			5,
+			proof.clone()
+		));
+		assert!(IntegrationWithReclaim::is_verified_for(&5, &provider_hash()));
+
+		// The same claim can't be replayed
+		assert_eq!(validate(5, &proof), Err(InvalidTransaction::Stale.into()));
+		assert_err!(
+			IntegrationWithReclaim::verify_user_unsigned(RawOrigin::None.into(), 5, proof),
+			Error::<Test>::ClaimAlreadyConsumed
+		);
+	})
+}
+
+#[test]
+fn should_reject_unsigned_verifications_dispatch_would_reject() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		let proof = setup_proof(5);
+		let validate = |account_id: u64, proof: &Proof| {
+			let call = Call::verify_user_unsigned { account_id, proof: proof.clone() };
+			IntegrationWithReclaim::validate_unsigned(TransactionSource::External, &call)
+		};
+
+		// The identity is already bound to another account
+		assert_ok!(IntegrationWithReclaim::verify_user(
+			RawOrigin::Signed(6).into(),
+			proof_for(6, "76561199601812329")
 		));
-		let claim_info = ClaimInfo {
-            provider: "http".to_string(),
-            parameters: "{\"body\":\"\",\"geoLocation\":\"in\",\"method\":\"GET\",\"responseMatches\":[{\"type\":\"regex\",\"value\":\"_steamid\\\">Steam ID: (?<CLAIM_DATA>.*)</div>\"}],\"responseRedactions\":[{\"jsonPath\":\"\",\"regex\":\"_steamid\\\">Steam ID: (?<CLAIM_DATA>.*)</div>\",\"xPath\":\"id(\\\"responsive_page_template_content\\\")/div[@class=\\\"page_header_ctn\\\"]/div[@class=\\\"page_content\\\"]/div[@class=\\\"youraccount_steamid\\\"]\"}],\"url\":\"https://store.steampowered.com/account/\"}".to_string(),
-            context: "{\"contextAddress\":\"user's address\",\"contextMessage\":\"for acmecorp.com on 1st january\",\"extractedParameters\":{\"CLAIM_DATA\":\"76561199601812329\"},\"providerHash\":\"0xffd5f761e0fb207368d9ebf9689f077352ab5d20ae0a2c23584c2cd90fc1b1bf\"}".to_string(),
-        };
-		let complete_claim_data = CompleteClaimData {
-            identifier: "0xd1dcfc5338cb588396e44e6449e8c750bd4d76332c7e9440c92383382fced0fd"
-                .to_string(),
-            owner: "0x13239fc6bf3847dfedaf067968141ec0363ca42f".to_string(),
-            epoch: 1_u64,
-            timestampS: 1712174155_u64,
-        };
-
-		let mut sigs = Vec::<String>::new();
-
-        let str_signature = "2888485f650f8ed02d18e32dd9a1512ca05feb83fc2cbf2df72fd8aa4246c5ee541fa53875c70eb64d3de9143446229a250c7a762202b7cc289ed31b74b31c811c".to_string();
-
-        sigs.push(str_signature);
-
-        let signed_claim = SignedClaim {
-            claim: complete_claim_data,
-            signatures: sigs,
-        };
-
-		let proof = Proof {
-            claimInfo: claim_info,
-            signedClaim: signed_claim,
-        };
+		assert_eq!(validate(5, &proof), Err(InvalidTransaction::BadProof.into()));
+		assert_err!(
+			IntegrationWithReclaim::verify_user_unsigned(RawOrigin::None.into(), 5, proof),
+			Error::<Test>::IdentityAlreadyClaimed
+		);
 
+		// The claim has been revoked
+		let revoked = proof_for(5, "76561199601812330");
 		assert_ok!(IntegrationWithReclaim::verify_user(
-			RawOrigin::Signed(source_account_id).into(),
+			RawOrigin::Signed(5).into(),
+			revoked.clone()
+		));
+		let claim_identifier = IntegrationWithReclaim::claim_identifier(&revoked).unwrap();
+		let attesting = Reclaim::attesting_witnesses(&revoked);
+		let signatures = BoundedVec::truncate_from(
+			(0..5)
+				.filter(|index| attesting.contains(&proof_builder::witness(*index).address))
+				.map(|index| {
+					proof_builder::sign_revocation(
+						&claim_identifier,
+						&proof_builder::witness_key(index),
+					)
+				})
+				.collect(),
+		);
+		assert_ok!(Reclaim::revoke_claim(
+			RawOrigin::Signed(1).into(),
+			claim_identifier,
+			signatures
+		));
+		assert_eq!(validate(5, &revoked), Err(InvalidTransaction::BadProof.into()));
+
+		// The account has no proof submissions left
+		ProofAttemptWindow::set(10);
+		MaxProofAttempts::set(1);
+		let rate_limited = proof_for(7, "76561199601812331");
+		assert_eq!(validate(7, &rate_limited).map(|_| ()), Ok(()));
+		Reclaim::note_proof_attempt(&7, true);
+		assert_eq!(validate(7, &rate_limited), Err(InvalidTransaction::BadProof.into()));
+		assert_eq!(
+			IntegrationWithReclaim::verify_user_unsigned(RawOrigin::None.into(), 7, rate_limited)
+				.unwrap_err()
+				.error,
+			pallet_reclaim::Error::<Test>::RateLimited.into()
+		);
+	})
+}
+
+#[test]
+fn should_verify_user_unsigned_without_deposit() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		VerifiedClaimDeposit::set(Some(100));
+		let proof = setup_proof(5);
+		let claim_identifier = IntegrationWithReclaim::claim_identifier(&proof).unwrap();
+
+		// Signed verifications reserve the claim deposit from the submitter
+		assert!(IntegrationWithReclaim::verify_user(RawOrigin::Signed(5).into(), proof.clone())
+			.is_err());
+
+		// The account of an unsigned verification may hold no funds
+		assert_ok!(IntegrationWithReclaim::verify_user_unsigned(
+			RawOrigin::None.into(),
+			5,
 			proof
 		));
+		assert!(IntegrationWithReclaim::is_verified_for(&5, &provider_hash()));
+		assert_eq!(Balances::reserved_balance(5), 0);
+		assert_eq!(Reclaim::verified_claims(claim_identifier), None);
+	})
+}
 
-		assert_eq!(IntegrationWithReclaim::account_verified(source_account_id), Some(true));
+#[test]
+fn should_require_reverification_after_witness_revocation() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		let proof = setup_proof(5);
+		assert_ok!(IntegrationWithReclaim::verify_user(RawOrigin::Signed(5).into(), proof.clone()));
+		let record = IntegrationWithReclaim::account_verified(&5, &provider_hash()).unwrap();
+		assert_eq!(record.witnesses.into_inner(), Reclaim::attesting_witnesses(&proof));
+		assert!(!IntegrationWithReclaim::needs_reverification(&5, &provider_hash()));
+
+		// Revoking a witness that attested the claim invalidates the verification
+		assert_ok!(Reclaim::revoke_witness(RawOrigin::Root.into(), record.witnesses[0]));
+		assert!(IntegrationWithReclaim::needs_reverification(&5, &provider_hash()));
+		assert!(!IntegrationWithReclaim::is_verified_for(&5, &provider_hash()));
+		assert!(!IntegrationWithReclaim::is_verified(&5));
+		assert!(pallet::AccountVerified::<Test>::contains_key(5, provider_hash()));
+	})
+}
+
+#[test]
+fn should_remove_verifications_of_revoked_claims() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		let proof = setup_proof(5);
+		assert_ok!(IntegrationWithReclaim::verify_user(RawOrigin::Signed(5).into(), proof));
+		let record = IntegrationWithReclaim::account_verified(&5, &provider_hash()).unwrap();
+		// The witnesses that attested the claim revoke it, even though it isn't stored
+		let signatures = BoundedVec::truncate_from(
+			(0..5)
+				.filter(|index| record.witnesses.contains(&proof_builder::witness(*index).address))
+				.map(|index| {
+					proof_builder::sign_revocation(
+						&record.claim_identifier,
+						&proof_builder::witness_key(index),
+					)
+				})
+				.collect(),
+		);
+
+		assert_ok!(Reclaim::revoke_claim(
+			RawOrigin::Signed(1).into(),
+			record.claim_identifier,
+			signatures
+		));
+		System::assert_has_event(
+			Event::VerificationRevoked { account_id: 5, provider_hash: provider_hash() }.into(),
+		);
+		assert!(!pallet::AccountVerified::<Test>::contains_key(5, provider_hash()));
+		assert!(!IntegrationWithReclaim::is_verified(&5));
+	})
+}
+
+#[test]
+fn should_unverify_every_account_verified_with_a_revoked_claim() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		// Without unique identities, several accounts can verify with the same claim
+		UniqueIdentityParameter::set(None);
+		let proof = setup_proof(5);
+		let claim_identifier = IntegrationWithReclaim::claim_identifier(&proof).unwrap();
+		assert_ok!(IntegrationWithReclaim::verify_user(RawOrigin::Signed(5).into(), proof.clone()));
+		assert_ok!(IntegrationWithReclaim::verify_user(RawOrigin::Signed(6).into(), proof.clone()));
+		assert!(IntegrationWithReclaim::is_verified(&5));
+		assert!(IntegrationWithReclaim::is_verified(&6));
+
+		let attesting = Reclaim::attesting_witnesses(&proof);
+		let signatures = BoundedVec::truncate_from(
+			(0..5)
+				.filter(|index| attesting.contains(&proof_builder::witness(*index).address))
+				.map(|index| {
+					proof_builder::sign_revocation(
+						&claim_identifier,
+						&proof_builder::witness_key(index),
+					)
+				})
+				.collect(),
+		);
+		assert_ok!(Reclaim::revoke_claim(
+			RawOrigin::Signed(1).into(),
+			claim_identifier,
+			signatures
+		));
+
+		// The record of the submitter is removed, the other one no longer counts
+		assert!(!pallet::AccountVerified::<Test>::contains_key(5, provider_hash()));
+		assert!(pallet::AccountVerified::<Test>::contains_key(6, provider_hash()));
+		for account_id in [5, 6] {
+			assert!(!IntegrationWithReclaim::is_verified(&account_id));
+			assert!(!IntegrationWithReclaim::is_verified_for(&account_id, &provider_hash()));
+			assert!(EnsureVerified::<Test>::try_origin(RawOrigin::Signed(account_id).into())
+				.is_err());
+		}
+		assert!(IntegrationWithReclaim::needs_reverification(&6, &provider_hash()));
 	})
 }
+
+#[test]
+fn should_verify_user_for_authorizing_account() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		VerifiedClaimDeposit::set(Some(100));
+		RelayedClaimDeposit::set(10);
+		Balances::make_free_balance_be(&7, 100);
+		let proof = setup_proof(5);
+		let claim_identifier = IntegrationWithReclaim::claim_identifier(&proof).unwrap();
+		let genesis_hash = System::block_hash(0);
+		let authorization = |signer: u64, nonce: u64| {
+			TestSignature(signer, authorization_message(&genesis_hash, &claim_identifier, nonce))
+		};
+
+		// The authorization must be signed by the target over its current nonce on this chain
+		let other_chain =
+			TestSignature(5, authorization_message(&H256::repeat_byte(1), &claim_identifier, 0));
+		for bad in [authorization(6, 0), authorization(5, 1), other_chain] {
+			assert_err!(
+				IntegrationWithReclaim::verify_user_for(
+					RawOrigin::Signed(7).into(),
+					5,
+					proof.clone(),
+					bad
+				),
+				Error::<Test>::InvalidAuthorization
+			);
+		}
+		assert_ok!(IntegrationWithReclaim::verify_user_for(
+			RawOrigin::Signed(7).into(),
+			5,
+			proof.clone(),
+			authorization(5, 0)
+		));
+		assert!(IntegrationWithReclaim::is_verified_for(&5, &provider_hash()));
+		assert!(!IntegrationWithReclaim::is_verified(&7));
+		// Only the relayed claim deposit is reserved from the relayer
+		assert_eq!(Reclaim::claim_attestations(claim_identifier).unwrap().claim.submitter, 7);
+		assert_eq!(Reclaim::verified_claims(claim_identifier), None);
+		assert_eq!(Balances::reserved_balance(7), 10);
+
+		// The authorization can't be replayed
+		assert_eq!(IntegrationWithReclaim::authorization_nonce(5), 1);
+		assert_err!(
+			IntegrationWithReclaim::verify_user_for(
+				RawOrigin::Signed(7).into(),
+				5,
+				proof,
+				authorization(5, 0)
+			),
+			Error::<Test>::InvalidAuthorization
+		);
+
+		// Revoking the claim removes the verification of the target
+		let signatures = BoundedVec::truncate_from(
+			[0, 1]
+				.iter()
+				.map(|index| {
+					proof_builder::sign_revocation(
+						&claim_identifier,
+						&proof_builder::witness_key(*index),
+					)
+				})
+				.collect(),
+		);
+		assert_ok!(Reclaim::revoke_claim(RawOrigin::Signed(1).into(), claim_identifier, signatures));
+		System::assert_has_event(
+			Event::VerificationRevoked { account_id: 5, provider_hash: provider_hash() }.into(),
+		);
+		assert!(!pallet::AccountVerified::<Test>::contains_key(5, provider_hash()));
+		assert_eq!(Balances::reserved_balance(7), 0);
+	})
+}
+
+#[test]
+fn should_release_relayer_deposit_when_verification_expires() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		VerifiedClaimDeposit::set(Some(0));
+		RelayedClaimDeposit::set(10);
+		Balances::make_free_balance_be(&7, 100);
+		let proof = setup_proof(5);
+		let claim_identifier = IntegrationWithReclaim::claim_identifier(&proof).unwrap();
+		let message = authorization_message(&System::block_hash(0), &claim_identifier, 0);
+		assert_ok!(IntegrationWithReclaim::verify_user_for(
+			RawOrigin::Signed(7).into(),
+			5,
+			proof,
+			TestSignature(5, message)
+		));
+		assert_eq!(Balances::reserved_balance(7), 10);
+
+		IntegrationWithReclaim::on_idle(101, Weight::MAX);
+		assert!(!pallet::AccountVerified::<Test>::contains_key(5, provider_hash()));
+		assert!(!pallet::RelayedClaims::<Test>::contains_key(claim_identifier));
+		assert_eq!(Balances::reserved_balance(7), 0);
+	})
+}
+
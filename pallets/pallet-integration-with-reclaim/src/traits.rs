@@ -0,0 +1,11 @@
+use pallet_reclaim::ProviderHash;
+
+pub trait VerificationInspect<AccountId> {
+	fn is_verified_for(who: &AccountId, provider: &ProviderHash) -> bool;
+}
+
+impl<AccountId> VerificationInspect<AccountId> for () {
+	fn is_verified_for(_who: &AccountId, _provider: &ProviderHash) -> bool {
+		false
+	}
+}
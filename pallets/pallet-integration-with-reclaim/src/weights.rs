@@ -1,3 +1,10 @@
+//! Weights for `pallet_integration_with_reclaim`
+//!
+//! Every entry is a placeholder estimated by hand from the storage it accesses. Regenerate this
+//! file with `benchmark pallet` before the weights are relied on. The verification benchmarks
+//! measure the whole call, proof verification included, so regenerated weights of these calls
+//! overestimate their own work.
+
 #![cfg_attr(rustfmt, rustfmt_skip)]
 #![allow(unused_parens)]
 #![allow(unused_imports)]
@@ -7,28 +14,66 @@ use frame_support::traits::Get;
 use core::marker::PhantomData;
 use frame_support::weights::Weight;
 
+/// Weights of the calls of the pallet. Verification calls add the worst-case weight of
+/// `ReclaimVerifier::verify_proof` to the weights below, which only cover their own work.
 pub trait WeightInfo {
 	fn verify_user() -> Weight;
+
+	fn reassign_identity() -> Weight;
+
+	fn verify_user_unsigned() -> Weight;
+
+	fn verify_user_for() -> Weight;
 }
 
 /// Weight functions for `pallet_reclaim`.
 pub struct SubstrateWeightInfo<T>(PhantomData<T>);
 impl<T: frame_system::Config> WeightInfo for SubstrateWeightInfo<T> {
-	/// Storage: `Reclaim::PReclaimConfig` (r:1 w:0)
-	/// Proof: `Reclaim::PReclaimConfig` (`max_values`: Some(1), `max_size`: Some(40), added: 535, mode: `MaxEncodedLen`)
-	/// Storage: `Reclaim::Epochs` (r:1 w:0)
-	/// Proof: `Reclaim::Epochs` (`max_values`: None, `max_size`: Some(5266), added: 7741, mode: `MaxEncodedLen`)
-	/// Storage: `IntegrationWithReclaim::AccountVerified` (r:0 w:1)
-	/// Proof: `IntegrationWithReclaim::AccountVerified` (`max_values`: None, `max_size`: Some(33), added: 2508, mode: `MaxEncodedLen`)
+	/// Placeholder estimated by hand, not benchmarked.
+	/// Storage: `IntegrationWithReclaim::UniqueIdentities` (r:1 w:1)
+	/// Storage: `IntegrationWithReclaim::AccountVerified` (r:1 w:1)
+	/// Storage: `IntegrationWithReclaim::RelayedClaims` (r:1 w:1)
+	/// Storage: `System::Account` (r:1 w:1)
 	fn verify_user() -> Weight {
-		// Proof Size summary in bytes:
-		//  Measured:  `210`
-		//  Estimated: `8731`
-		// Minimum execution time: 488_000_000 picoseconds.
-		Weight::from_parts(496_000_000, 0)
-			.saturating_add(Weight::from_parts(0, 8731))
-			.saturating_add(T::DbWeight::get().reads(2))
-			.saturating_add(T::DbWeight::get().writes(1))
+		Weight::from_parts(28_000_000, 0)
+			.saturating_add(Weight::from_parts(0, 3593))
+			.saturating_add(T::DbWeight::get().reads(4))
+			.saturating_add(T::DbWeight::get().writes(4))
+	}
+	/// Placeholder estimated by hand, not benchmarked.
+	/// Storage: `IntegrationWithReclaim::UniqueIdentities` (r:1 w:1)
+	/// Storage: `IntegrationWithReclaim::AccountVerified` (r:1 w:1)
+	/// Storage: `IntegrationWithReclaim::RelayedClaims` (r:1 w:1)
+	/// Storage: `System::Account` (r:1 w:1)
+	fn reassign_identity() -> Weight {
+		Weight::from_parts(24_000_000, 0)
+			.saturating_add(Weight::from_parts(0, 3593))
+			.saturating_add(T::DbWeight::get().reads(4))
+			.saturating_add(T::DbWeight::get().writes(4))
+	}
+	/// Placeholder estimated by hand, not benchmarked.
+	/// Storage: `IntegrationWithReclaim::ConsumedClaims` (r:1 w:1)
+	/// Storage: `IntegrationWithReclaim::UniqueIdentities` (r:1 w:1)
+	/// Storage: `IntegrationWithReclaim::AccountVerified` (r:1 w:1)
+	/// Storage: `IntegrationWithReclaim::RelayedClaims` (r:1 w:1)
+	/// Storage: `System::Account` (r:1 w:1)
+	fn verify_user_unsigned() -> Weight {
+		Weight::from_parts(32_000_000, 0)
+			.saturating_add(Weight::from_parts(0, 3593))
+			.saturating_add(T::DbWeight::get().reads(5))
+			.saturating_add(T::DbWeight::get().writes(5))
+	}
+	/// Placeholder estimated by hand, not benchmarked.
+	/// Storage: `IntegrationWithReclaim::AuthorizationNonces` (r:1 w:1)
+	/// Storage: `IntegrationWithReclaim::UniqueIdentities` (r:1 w:1)
+	/// Storage: `IntegrationWithReclaim::AccountVerified` (r:1 w:1)
+	/// Storage: `IntegrationWithReclaim::RelayedClaims` (r:2 w:2)
+	/// Storage: `System::Account` (r:2 w:2)
+	fn verify_user_for() -> Weight {
+		Weight::from_parts(82_000_000, 0)
+			.saturating_add(Weight::from_parts(0, 3593))
+			.saturating_add(T::DbWeight::get().reads(7))
+			.saturating_add(T::DbWeight::get().writes(7))
 	}
 }
 
@@ -38,4 +83,16 @@ impl WeightInfo for () {
 		Weight::from_parts(9_000_000, 0)
 	}
 
+	fn reassign_identity() -> Weight {
+		Weight::from_parts(9_000_000, 0)
+	}
+
+	fn verify_user_unsigned() -> Weight {
+		Weight::from_parts(9_000_000, 0)
+	}
+
+	fn verify_user_for() -> Weight {
+		Weight::from_parts(9_000_000, 0)
+	}
+
 }
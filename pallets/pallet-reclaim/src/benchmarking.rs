@@ -3,16 +3,22 @@
 use super::*;
 
 use crate::Pallet as Reclaim;
-use frame_benchmarking::{account, benchmarks};
-use frame_support::assert_ok;
-use frame_system::RawOrigin;
+use frame_benchmarking::{account, benchmarks, BenchmarkError};
+use frame_support::{
+	assert_ok,
+	traits::{Currency, EnsureOrigin, Get, Hooks, ReservableCurrency},
+};
+use frame_system::{pallet_prelude::BlockNumberFor, RawOrigin};
+use sp_runtime::traits::{Bounded, Zero};
 
 benchmarks! {
   // Benchmark for initializing the Reclaim pallet
   init {
 	  let src_account: T::AccountId = account("acc1", 0,0);
 	  let current_epoch: u128 = 0;
-  }:_(RawOrigin::Signed(src_account.clone()))
+	  let init_origin = T::InitOrigin::try_successful_origin()
+		  .map_err(|_| BenchmarkError::Weightless)?;
+  }:_<T::RuntimeOrigin>(init_origin, src_account.clone())
   verify {
 	  // Verify that the Reclaim configuration is set correctly
 	  assert_eq!(Reclaim::<T>::reclaim_config(), Some(ReclaimConfig{
@@ -26,7 +32,9 @@ benchmarks! {
       	// Create a source account for the test
 	let source_account_id: T::AccountId = account("acc1", 0,0);
       	// Initialize the Reclaim pallet
-	assert_ok!(Reclaim::<T>::init(RawOrigin::Signed(source_account_id.clone()).into()));
+	let init_origin = T::InitOrigin::try_successful_origin()
+		.map_err(|_| BenchmarkError::Weightless)?;
+	assert_ok!(Reclaim::<T>::init(init_origin, source_account_id.clone()));
 		let addr = hex::decode("244897572368eadf65bfbc5aec98d8e5443a9072").unwrap();
 		let addr_slice = addr.get(0..20).unwrap();
 
@@ -52,67 +60,301 @@ benchmarks! {
 			minimum_witness
 		));
   }
-  // Benchmark for verifying a proof
+  // Benchmark for verifying a proof with `s` signatures, `w` witnesses and `l` bytes of claim
   verify_proof{
+	let s in 1 .. MAX_WITNESSES;
+	let w in 1 .. MAX_WITNESSES;
+	let l in 0 .. 102_400;
 	let source_account_id: T::AccountId = account("acc1", 0,0);
-	assert_ok!(Reclaim::<T>::init(RawOrigin::Signed(source_account_id.clone()).into()));
-	        // Decode a sample address
-		let addr = hex::decode("244897572368eadf65bfbc5aec98d8e5443a9072").unwrap();
-		let addr_slice = addr.get(0..20).unwrap();
-
-	  	// Normalize the address to a fixed array format
-		let mut addr_normzlized: [u8; 20] = [0_u8; 20];
-
-		for i in 0..20 {
-			addr_normzlized[i] = addr_slice[i]
-		}
-      		// Create a sample witness
-		let w1 = Witness { address: addr_normzlized, host: [1_u8; 32] };
-      		// Add the witness to a vector and convert it to a BoundedVec
-		let mut witnesses_vec = Vec::<Witness>::new();
-		witnesses_vec.push(w1);
-		let witnesses: BoundedVec<Witness, ConstU32<100>> =
-			BoundedVec::<Witness, ConstU32<100>>::try_from(witnesses_vec).unwrap();
-	        // Set the minimum number of witnesses required
-		let minimum_witness = 1;
+	// Fund the submitter for the deposit of the verified claim
+	T::Currency::make_free_balance_be(&source_account_id, BalanceOf::<T>::max_value() / 2_u32.into());
+	let init_origin = T::InitOrigin::try_successful_origin()
+		.map_err(|_| BenchmarkError::Weightless)?;
+	assert_ok!(Reclaim::<T>::init(init_origin, source_account_id.clone()));
+		// Add an epoch of `w` generated witnesses, `s` of which sign each claim. An epoch can't
+		// require more signatures than it has witnesses, so `w` is raised to `s` when below it
 		assert_ok!(Reclaim::<T>::add_epoch(
 			RawOrigin::Signed(source_account_id.clone()).into(),
-			witnesses,
-			minimum_witness
+			proof_builder::witnesses(w.max(s)),
+			s
 		));
-	        // Create sample claim information
+		// Pad the parameters so the claim takes `l` bytes
+		let context = proof_builder::claim_context("0x", "");
+		let padding = (l as usize).saturating_sub(context.len() + 4);
 		let claim_info = ClaimInfo {
 			provider: String::from("http"),
-			parameters: String::from("{\"body\":\"\",\"geoLocation\":\"in\",\"method\":\"GET\",\"responseMatches\":[{\"type\":\"regex\",\"value\":\"_steamid\\\">Steam ID: (?<CLAIM_DATA>.*)</div>\"}],\"responseRedactions\":[{\"jsonPath\":\"\",\"regex\":\"_steamid\\\">Steam ID: (?<CLAIM_DATA>.*)</div>\",\"xPath\":\"id(\\\"responsive_page_template_content\\\")/div[@class=\\\"page_header_ctn\\\"]/div[@class=\\\"page_content\\\"]/div[@class=\\\"youraccount_steamid\\\"]\"}],\"url\":\"https://store.steampowered.com/account/\"}"),
-			context: String::from("{\"contextAddress\":\"user's address\",\"contextMessage\":\"for acmecorp.com on 1st january\",\"extractedParameters\":{\"CLAIM_DATA\":\"76561199601812329\"},\"providerHash\":\"0xffd5f761e0fb207368d9ebf9689f077352ab5d20ae0a2c23584c2cd90fc1b1bf\"}"),
+			parameters: "a".repeat(padding),
+			context,
 		};
-	        // Create a sample signed claim
-		let complete_claim_data = CompleteClaimData {
-			identifier: String::from("0xd1dcfc5338cb588396e44e6449e8c750bd4d76332c7e9440c92383382fced0fd"),
-			owner: String::from("0x13239fc6bf3847dfedaf067968141ec0363ca42f"),
-			epoch: 1_u64,
-			timestampS: 1712174155_u64,
+		// Have the witnesses selected for the claim sign it
+		let Proof { claimInfo: claim_info, signedClaim: signed_claim } =
+			proof_builder::build_proof(&Reclaim::<T>::epochs(1).unwrap(), claim_info, 1712174155);
+		let verified = Event::<T>::ProofVerified {
+			who: source_account_id.clone(),
+			identifier: signed_claim.claim.identifier.clone(),
+			owner: signed_claim.claim.owner.clone(),
+			provider_hash: claim_info.provider_hash(),
+			epoch_id: 1,
+			timestamp: 1712174155,
 		};
-
-		let mut sigs = Vec::<String>::new();
-
-		let str_signature = String::from("2888485f650f8ed02d18e32dd9a1512ca05feb83fc2cbf2df72fd8aa4246c5ee541fa53875c70eb64d3de9143446229a250c7a762202b7cc289ed31b74b31c811c");
-
-		sigs.push(str_signature);
-
-		let signed_claim = SignedClaim {
-			claim: complete_claim_data,
-			signatures: sigs,
-		};
-  } : {
-	        // Verify the proof using the provided claim and signature
-		assert_ok!(Reclaim::<T>::verify_proof(
+  }: _(RawOrigin::Signed(source_account_id), claim_info, signed_claim)
+  verify {
+	  // Verify that the proof was accepted in the current epoch
+	  frame_system::Pallet::<T>::assert_last_event(
+		  <T as Config>::RuntimeEvent::from(verified).into()
+	  );
+  }
+  // Benchmark for removing a verified claim
+  remove_verified_claim{
+	let submitter: T::AccountId = account("acc1", 0,0);
+	let deposit = T::VerifiedClaimDeposit::get().unwrap_or_default();
+	T::Currency::make_free_balance_be(&submitter, BalanceOf::<T>::max_value() / 2_u32.into());
+	assert_ok!(T::Currency::reserve(&submitter, deposit));
+	let identifier = [1_u8; 32];
+	VerifiedClaims::<T>::insert(identifier, VerifiedClaim {
+		submitter: submitter.clone(),
+		owner: BoundedVec::truncate_from(vec![1_u8; 64]),
+		epoch: 1,
+		block: frame_system::Pallet::<T>::block_number(),
+		provider_hash: Some([1_u8; 32]),
+		parameters_hash: Default::default(),
+		deposit,
+	});
+  }:_(RawOrigin::Signed(submitter), identifier)
+  verify {
+	  // Verify that the claim is removed
+	  assert_eq!(Reclaim::<T>::verified_claims(identifier), None);
+  }
+  // Benchmark for revoking a verified claim with the signatures of `s` witnesses
+  revoke_claim{
+	let s in 1 .. MAX_WITNESSES;
+	let source_account_id: T::AccountId = account("acc1", 0,0);
+	let init_origin = T::InitOrigin::try_successful_origin()
+		.map_err(|_| BenchmarkError::Weightless)?;
+	assert_ok!(Reclaim::<T>::init(init_origin, source_account_id.clone()));
+	assert_ok!(Reclaim::<T>::add_epoch(
+		RawOrigin::Signed(source_account_id.clone()).into(),
+		proof_builder::witnesses(s),
+		s
+	));
+	let identifier = [1_u8; 32];
+	// Have the claim attested by `s` witnesses of the epoch
+	ClaimAttestations::<T>::insert(identifier, ClaimAttestation {
+		claim: VerifiedClaim {
+			submitter: source_account_id.clone(),
+			owner: BoundedVec::truncate_from(vec![1_u8; 20]),
+			epoch: 1,
+			block: frame_system::Pallet::<T>::block_number(),
+			provider_hash: Some([1_u8; 32]),
+			parameters_hash: Default::default(),
+			deposit: Default::default(),
+		},
+		timestamp: 1712174155,
+	});
+	// Have every witness of the epoch sign the revocation
+	let signatures: BoundedVec<String, ConstU32<100>> = BoundedVec::truncate_from(
+		(0..s)
+			.map(|index| {
+				proof_builder::sign_revocation(&identifier, &proof_builder::witness_key(index))
+			})
+			.collect(),
+	);
+  }:_(RawOrigin::Signed(source_account_id), identifier, signatures)
+  verify {
+	  // Verify that the claim is revoked
+	  assert!(Reclaim::<T>::is_claim_revoked(&identifier));
+  }
+  // Benchmark for setting the epoch syncers
+  set_epoch_syncers{
+	let source_account_id: T::AccountId = account("acc1", 0,0);
+	let init_origin = T::InitOrigin::try_successful_origin()
+		.map_err(|_| BenchmarkError::Weightless)?;
+	assert_ok!(Reclaim::<T>::init(init_origin, source_account_id.clone()));
+		let syncer: T::AccountId = account("syncer", 0,0);
+		let syncers: BoundedVec<T::AccountId, ConstU32<10>> =
+			BoundedVec::<T::AccountId, ConstU32<10>>::try_from(vec![syncer]).unwrap();
+  }:_(RawOrigin::Signed(source_account_id), syncers.clone())
+  verify {
+	  // Verify that the syncers are stored
+	  assert_eq!(Reclaim::<T>::epoch_syncers(), syncers);
+  }
+  // Benchmark for transferring the ownership
+  transfer_ownership{
+	let source_account_id: T::AccountId = account("acc1", 0,0);
+	let init_origin = T::InitOrigin::try_successful_origin()
+		.map_err(|_| BenchmarkError::Weightless)?;
+	assert_ok!(Reclaim::<T>::init(init_origin, source_account_id.clone()));
+		let new_owner: T::AccountId = account("acc2", 0,0);
+  }:_(RawOrigin::Signed(source_account_id), new_owner.clone())
+  verify {
+	  // Verify that the new owner is stored
+	  assert_eq!(Reclaim::<T>::reclaim_config().unwrap().owner, new_owner);
+  }
+  // Benchmark for pausing proof verification
+  pause{
+	let origin = T::AdminOrigin::try_successful_origin()
+		.map_err(|_| BenchmarkError::Weightless)?;
+  }:_<T::RuntimeOrigin>(origin, PauseScope::All)
+  verify {
+	  // Verify that verification is paused
+	  assert!(Reclaim::<T>::is_paused(PauseScope::All));
+  }
+  // Benchmark for resuming proof verification
+  unpause{
+	let origin = T::AdminOrigin::try_successful_origin()
+		.map_err(|_| BenchmarkError::Weightless)?;
+	assert_ok!(Reclaim::<T>::pause(origin.clone(), PauseScope::All));
+  }:_<T::RuntimeOrigin>(origin, PauseScope::All)
+  verify {
+	  // Verify that verification is resumed
+	  assert!(!Reclaim::<T>::is_paused(PauseScope::All));
+  }
+  // Benchmark for revoking a witness
+  revoke_witness{
+	let origin = T::AdminOrigin::try_successful_origin()
+		.map_err(|_| BenchmarkError::Weightless)?;
+	let address = [1_u8; 20];
+  }:_<T::RuntimeOrigin>(origin, address)
+  verify {
+	  // Verify that the witness is revoked
+	  assert!(Reclaim::<T>::is_witness_revoked(&address));
+  }
+  // Benchmark for syncing an epoch as an epoch syncer
+  sync_epoch{
+	let source_account_id: T::AccountId = account("acc1", 0,0);
+	let init_origin = T::InitOrigin::try_successful_origin()
+		.map_err(|_| BenchmarkError::Weightless)?;
+	assert_ok!(Reclaim::<T>::init(init_origin, source_account_id.clone()));
+		// Register the syncer that submits the epoch
+		let syncer: T::AccountId = account("syncer", 0,0);
+		let syncers: BoundedVec<T::AccountId, ConstU32<10>> =
+			BoundedVec::<T::AccountId, ConstU32<10>>::try_from(vec![syncer.clone()]).unwrap();
+		assert_ok!(Reclaim::<T>::set_epoch_syncers(
 			RawOrigin::Signed(source_account_id).into(),
-			claim_info,
-			signed_claim
+			syncers
 		));
+		// Sign the epoch with the key of the epoch source signer listed by the runtime
+		let witnesses = proof_builder::witnesses(1);
+		let minimum_witness = 1;
+		let genesis_hash = frame_system::Pallet::<T>::block_hash(BlockNumberFor::<T>::zero());
+		let signature = proof_builder::sign_epoch(
+			genesis_hash.as_ref(),
+			1,
+			&witnesses,
+			minimum_witness,
+			&proof_builder::witness_key(proof_builder::EPOCH_SOURCE_KEY),
+		);
+  }:_(RawOrigin::Signed(syncer), witnesses, minimum_witness, signature)
+  verify {
+	  // Verify that the synced epoch became current
+	  assert_eq!(Reclaim::<T>::reclaim_config().unwrap().current_epoch, 1_u64);
+  }
+  // Benchmark for submitting an optimistic proof of `l` bytes once encoded
+  submit_optimistic{
+	let l in 0 .. 102_400;
+	T::OptimisticBond::get().ok_or(BenchmarkError::Weightless)?;
+	let source_account_id: T::AccountId = account("acc1", 0,0);
+	T::Currency::make_free_balance_be(&source_account_id, BalanceOf::<T>::max_value() / 2_u32.into());
+	let init_origin = T::InitOrigin::try_successful_origin()
+		.map_err(|_| BenchmarkError::Weightless)?;
+	assert_ok!(Reclaim::<T>::init(init_origin, source_account_id.clone()));
+		assert_ok!(Reclaim::<T>::add_epoch(
+			RawOrigin::Signed(source_account_id.clone()).into(),
+			proof_builder::witnesses(MAX_WITNESSES),
+			MAX_WITNESSES
+		));
+		// Pad the parameters so the encoded proof takes `l` bytes, up to the maximum size,
+		// leaving room for the longer length prefix of the padded parameters
+		let epoch = Reclaim::<T>::epochs(1).unwrap();
+		let build = |parameters: String| {
+			let context = proof_builder::claim_context("0x", "");
+			let claim_info = ClaimInfo { provider: String::from("http"), parameters, context };
+			proof_builder::build_proof(&epoch, claim_info, 1712174155)
+		};
+		let size = l.min(T::MaxOptimisticProofSize::get()) as usize;
+		let overhead = build(String::new()).encoded_size() + 4;
+		let Proof { claimInfo: claim_info, signedClaim: signed_claim } =
+			build("a".repeat(size.saturating_sub(overhead)));
+  }: _(RawOrigin::Signed(source_account_id), claim_info, signed_claim)
+  verify {
+	  // Verify that the proof is pending
+	  assert!(Reclaim::<T>::pending_proofs(0).is_some());
+  }
+  // Benchmark for challenging an invalid optimistic proof, excluding its verification
+  challenge{
+	let bond = T::OptimisticBond::get().ok_or(BenchmarkError::Weightless)?;
+	let source_account_id: T::AccountId = account("acc1", 0,0);
+	T::Currency::make_free_balance_be(&source_account_id, BalanceOf::<T>::max_value() / 2_u32.into());
+	let init_origin = T::InitOrigin::try_successful_origin()
+		.map_err(|_| BenchmarkError::Weightless)?;
+	assert_ok!(Reclaim::<T>::init(init_origin, source_account_id.clone()));
+		assert_ok!(Reclaim::<T>::add_epoch(
+			RawOrigin::Signed(source_account_id.clone()).into(),
+			proof_builder::witnesses(1),
+			1
+		));
+		// Tamper with a signed proof so that it fails its first check
+		let claim_info = proof_builder::http_claim("");
+		let mut proof =
+			proof_builder::build_proof(&Reclaim::<T>::epochs(1).unwrap(), claim_info, 1712174155);
+		proof.claimInfo.parameters = String::from("{\"tampered\":true}");
+		assert_ok!(T::Currency::reserve(&source_account_id, bond));
+		PendingProofs::<T>::insert(0, PendingProof {
+			submitter: source_account_id.clone(),
+			proof: proof.encode().try_into().map_err(|_| BenchmarkError::Weightless)?,
+			epoch: 1,
+			bond,
+			expires_at: frame_system::Pallet::<T>::block_number() + 1_u32.into(),
+			signatures: proof.signedClaim.signatures.len() as u32,
+		});
+		let challenger: T::AccountId = account("acc2", 0,0);
+  }: _(RawOrigin::Signed(challenger), 0, 1)
+  verify {
+	  // Verify that the proof is dropped and the bond slashed
+	  assert!(Reclaim::<T>::pending_proofs(0).is_none());
+	  assert!(T::Currency::reserved_balance(&source_account_id).is_zero());
+  }
+  // Benchmark for finalizing `p` optimistic proofs in `on_initialize`
+  finalize_pending_proofs{
+	let p in 0 .. T::MaxPendingPerBlock::get();
+	let source_account_id: T::AccountId = account("acc1", 0,0);
+	T::Currency::make_free_balance_be(&source_account_id, BalanceOf::<T>::max_value() / 2_u32.into());
+	let init_origin = T::InitOrigin::try_successful_origin()
+		.map_err(|_| BenchmarkError::Weightless)?;
+	assert_ok!(Reclaim::<T>::init(init_origin, source_account_id.clone()));
+		assert_ok!(Reclaim::<T>::add_epoch(
+			RawOrigin::Signed(source_account_id.clone()).into(),
+			proof_builder::witnesses(1),
+			1
+		));
+		let bond = T::OptimisticBond::get().unwrap_or_default();
+		let expires_at = frame_system::Pallet::<T>::block_number() + 1_u32.into();
+		// Queue `p` proofs of distinct claims, all finalized in the same block
+		for proof_id in 0..p {
+			let claim_info = proof_builder::http_claim(&format!("{}", proof_id));
+			let proof = proof_builder::build_proof(
+				&Reclaim::<T>::epochs(1).unwrap(),
+				claim_info,
+				1712174155,
+			);
+			assert_ok!(T::Currency::reserve(&source_account_id, bond));
+			PendingProofs::<T>::insert(proof_id as u64, PendingProof {
+				submitter: source_account_id.clone(),
+				proof: proof.encode().try_into().map_err(|_| BenchmarkError::Weightless)?,
+				epoch: 1,
+				bond,
+				expires_at,
+				signatures: proof.signedClaim.signatures.len() as u32,
+			});
+			assert_ok!(PendingExpiries::<T>::try_mutate(expires_at, |expiring| {
+				expiring.try_push(proof_id as u64)
+			}));
+		}
+  }: {
+	Reclaim::<T>::on_initialize(expires_at);
+  }
+  verify {
+	  // Verify that every proof is finalized
+	  assert_eq!(PendingProofs::<T>::iter_keys().count(), 0);
   }
-
-
-
 }
@@ -0,0 +1,146 @@
+//! Signed extension rejecting transactions that carry an invalid Reclaim proof.
+use crate::{traits::ProofCall, Call, Config, Pallet, Proof};
+use codec::{Decode, Encode};
+use core::marker::PhantomData;
+use frame_support::{dispatch::DispatchInfo, traits::IsSubType};
+use scale_info::TypeInfo;
+use sp_runtime::{
+	traits::{DispatchInfoOf, Dispatchable, PostDispatchInfoOf, SignedExtension},
+	transaction_validity::{
+		InvalidTransaction, TransactionValidity, TransactionValidityError, ValidTransaction,
+	},
+	DispatchResult,
+};
+
+/// Custom `InvalidTransaction` code of proof submissions beyond the rate limit of the sender.
+pub const RATE_LIMITED: u8 = 1;
+
+/// Checks the proof carried by `verify_proof`-like calls before they enter the transaction pool,
+/// so that invalid proofs are dropped without paying for their inclusion.
+///
+/// Full signature recovery only runs in `validate`; `pre_dispatch` repeats the cheap structural
+/// checks, as the call itself verifies the signatures again when dispatched.
+///
+/// Calls submitting proofs, including `verify_proofs` batches and optimistic proofs, are also
+/// refused once the sender reached its rate limit. The pallet counts the proofs of successful
+/// calls as it verifies them, while failed calls are rolled back and counted as a failure here.
+#[derive(Encode, Decode, Clone, Eq, PartialEq, TypeInfo)]
+#[scale_info(skip_type_params(T))]
+pub struct CheckReclaimProof<T: Config + Send + Sync>(PhantomData<T>);
+
+impl<T: Config + Send + Sync> CheckReclaimProof<T> {
+	/// Creates a new `CheckReclaimProof` extension.
+	pub fn new() -> Self {
+		Self(PhantomData)
+	}
+}
+
+impl<T: Config + Send + Sync> Default for CheckReclaimProof<T> {
+	fn default() -> Self {
+		Self::new()
+	}
+}
+
+impl<T: Config + Send + Sync> core::fmt::Debug for CheckReclaimProof<T> {
+	#[cfg(feature = "std")]
+	fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+		write!(f, "CheckReclaimProof")
+	}
+
+	#[cfg(not(feature = "std"))]
+	fn fmt(&self, _: &mut core::fmt::Formatter) -> core::fmt::Result {
+		Ok(())
+	}
+}
+
+impl<T: Config + Send + Sync> CheckReclaimProof<T>
+where
+	T::RuntimeCall: ProofCall<Proof> + IsSubType<Call<T>>,
+{
+	/// Returns the proof of a call, and whether the call submits any proof.
+	fn submitted_proof(call: &T::RuntimeCall) -> (Option<Proof>, bool) {
+		let proof = call.reclaim_proof();
+		let submits = proof.is_some() ||
+			matches!(
+				call.is_sub_type(),
+				Some(Call::verify_proofs { .. } | Call::submit_optimistic { .. })
+			);
+		(proof, submits)
+	}
+
+	/// Rejects submissions of `who` beyond its rate limit.
+	fn check_rate_limit(who: &T::AccountId) -> Result<(), TransactionValidityError> {
+		Pallet::<T>::ensure_within_rate_limit(who)
+			.map_err(|_| InvalidTransaction::Custom(RATE_LIMITED).into())
+	}
+}
+
+impl<T: Config + Send + Sync> SignedExtension for CheckReclaimProof<T>
+where
+	T::RuntimeCall: Dispatchable<Info = DispatchInfo> + ProofCall<Proof> + IsSubType<Call<T>>,
+{
+	const IDENTIFIER: &'static str = "CheckReclaimProof";
+	type AccountId = T::AccountId;
+	type Call = T::RuntimeCall;
+	type AdditionalSigned = ();
+	/// Account whose failed submission is counted against its rate limit
+	type Pre = Option<T::AccountId>;
+
+	fn additional_signed(&self) -> Result<(), TransactionValidityError> {
+		Ok(())
+	}
+
+	fn validate(
+		&self,
+		who: &Self::AccountId,
+		call: &Self::Call,
+		_info: &DispatchInfoOf<Self::Call>,
+		_len: usize,
+	) -> TransactionValidity {
+		let (proof, submits) = Self::submitted_proof(call);
+		if submits {
+			Self::check_rate_limit(who)?;
+		}
+		let Some(proof) = proof else { return Ok(ValidTransaction::default()) };
+		Pallet::<T>::check_proof(&proof, &mut Default::default())
+			.map_err(|_| InvalidTransaction::BadProof)?;
+
+		// The same proof is only accepted once in the pool
+		ValidTransaction::with_tag_prefix("ReclaimProof")
+			.and_provides(proof.signedClaim.claim.identifier)
+			.build()
+	}
+
+	fn pre_dispatch(
+		self,
+		who: &Self::AccountId,
+		call: &Self::Call,
+		_info: &DispatchInfoOf<Self::Call>,
+		_len: usize,
+	) -> Result<Self::Pre, TransactionValidityError> {
+		let (proof, submits) = Self::submitted_proof(call);
+		if !submits {
+			return Ok(None)
+		}
+		Self::check_rate_limit(who)?;
+		if let Some(proof) = proof {
+			Pallet::<T>::check_proof_structure(&proof, &mut Default::default())
+				.map_err(|_| InvalidTransaction::BadProof)?;
+		}
+		Ok(Some(who.clone()))
+	}
+
+	fn post_dispatch(
+		pre: Option<Self::Pre>,
+		_info: &DispatchInfoOf<Self::Call>,
+		_post_info: &PostDispatchInfoOf<Self::Call>,
+		_len: usize,
+		result: &DispatchResult,
+	) -> Result<(), TransactionValidityError> {
+		// Failed calls are rolled back, so their failure can only be recorded here
+		if let (Some(Some(who)), Err(_)) = (pre, result) {
+			Pallet::<T>::note_proof_attempt(&who, false);
+		}
+		Ok(())
+	}
+}
@@ -1,24 +1,30 @@
 #![cfg_attr(not(feature = "std"), no_std)]
 #![allow(non_snake_case, unused_imports)]
 
+extern crate alloc;
+
+use alloc::collections::BTreeMap;
 use codec::{Decode, Encode, MaxEncodedLen};
 use frame_support::{
-    dispatch::DispatchResult,
+    dispatch::{DispatchResult, DispatchResultWithPostInfo, WithPostDispatchInfo},
     ensure,
     pallet_prelude::ConstU32,
     sp_runtime::{
         traits::{IdentifyAccount, Verify},
-        BoundedVec, SaturatedConversion,
+        BoundedVec, DispatchError, SaturatedConversion,
     },
+    weights::Weight,
 };
 pub use pallet::*;
 use pallet_timestamp::{self as timestamp};
 use scale_info::prelude::{fmt::Debug, format, string::String, vec, vec::Vec};
+use serde::Deserialize;
 pub use weights::WeightInfo;
 
 use k256::ecdsa::{RecoveryId, Signature, VerifyingKey};
 use sha2::Sha256;
-use sha3::{Digest, Keccak256};
+use sha3::Digest;
+use sp_io::EcdsaVerifyError;
 
 use crate::identity_digest::Identity256;
 
@@ -33,8 +39,18 @@ mod benchmarking;
 pub mod weights;
 pub mod traits;
 mod identity_digest;
+pub mod offchain;
+mod check_proof;
+pub mod schemes;
+pub mod migrations;
+#[cfg(any(test, feature = "proof-builder"))]
+pub mod proof_builder;
 
-use traits::ReclaimVerifier;
+pub use check_proof::{CheckReclaimProof, RATE_LIMITED};
+pub use offchain::crypto;
+use traits::{
+    OnClaimRevoked, OnProofVerified, ProofCall, ReclaimVerifier, WitnessSignatureScheme,
+};
 
 /// Configuration structure for the Reclaim Protocol.
 #[derive(
@@ -73,7 +89,7 @@ pub struct Epoch {
     pub id: u64,                                      // Epoch identifier
     pub timestamp_start: u64,                         // Start timestamp
     pub timestamp_end: u64,                           // End timestamp
-    pub minimum_witness_for_claim_creation: u128,     // Minimum witnesses needed for claim creation
+    pub minimum_witness_for_claim_creation: u32,      // Minimum witnesses needed for claim creation
     pub witness: BoundedVec<Witness, ConstU32<100>>,  // List of witnesses for the epoch
 }
 
@@ -87,6 +103,13 @@ fn generate_random_seed(bytes: Vec<u8>, offset: usize) -> u32 {
     seed
 }
 
+/// Maximum number of witnesses in an epoch.
+pub const MAX_WITNESSES: u32 = 100;
+
+/// Number of blocks searched for room to reschedule an optimistic proof deferred while
+/// verification is paused.
+pub const MAX_DEFERRAL_BLOCKS: u32 = 16;
+
 /// Represents claim information, including provider, parameters, and context.
 #[derive(Encode, Decode, Eq, PartialEq, Clone, PartialOrd, Ord, scale_info::TypeInfo, Debug)]
 pub struct ClaimInfo {
@@ -98,13 +121,44 @@ pub struct ClaimInfo {
 impl ClaimInfo {
     /// Computes the hash of the claim info using Keccak256.
     pub fn hash(&self) -> String {
-        let mut hasher = Keccak256::new();
         let hash_str = format!("{}\n{}\n{}", &self.provider, &self.parameters, &self.context);
-        hasher.update(&hash_str);
-
-        let hash = hasher.finalize().to_vec();
+        let hash = sp_io::hashing::keccak_256(hash_str.as_bytes());
         append_0x(hex::encode(hash).as_str())
     }
+
+    /// Returns the combined length of the claim fields, in bytes.
+    pub fn size(&self) -> u32 {
+        (self.provider.len() + self.parameters.len() + self.context.len()).saturated_into()
+    }
+}
+
+/// Keccak256 hash identifying the provider a claim was made with.
+pub type ProviderHash = [u8; 32];
+
+/// Parsed form of the JSON stored in `ClaimInfo::context`.
+#[derive(Deserialize, Eq, PartialEq, Clone, Debug, Default)]
+#[serde(rename_all = "camelCase", default)]
+pub struct ClaimContext {
+    pub context_address: String,                         // Address the claim was generated for
+    pub context_message: String,                         // Free-form message of the claim
+    pub extracted_parameters: BTreeMap<String, String>,  // Parameters extracted from the response
+    pub provider_hash: String,                           // Hex-encoded hash of the provider
+}
+
+impl ClaimInfo {
+    /// Parses the context of the claim, if it is valid JSON.
+    pub fn parse_context(&self) -> Option<ClaimContext> {
+        serde_json::from_str(&self.context).ok()
+    }
+
+    /// Returns the provider hash declared in the context of the claim.
+    pub fn provider_hash(&self) -> Option<ProviderHash> {
+        let context = self.parse_context()?;
+        let mut provider_hash = [0_u8; 32];
+        hex::decode_to_slice(context.provider_hash.trim_start_matches("0x"), &mut provider_hash)
+            .ok()?;
+        Some(provider_hash)
+    }
 }
 
 /// Represents the complete data of a claim, including identifier and epoch.
@@ -131,49 +185,78 @@ pub struct SignedClaim {
 }
 
 impl SignedClaim {
-    /// Recovers the public keys of the signers from the signed claim.
-    pub fn recover_signers_of_signed_claim(self) -> Vec<Vec<u8>> {
-        let mut expected = vec![];
-        // Hash the serialized claim
-        let serialised_claim = self.claim.serialise();
-        let message_hash = keccak256_eth(serialised_claim.as_str()).to_vec();
-
-        // Process each signature to recover the corresponding public key
-        for complete_signature in self.signatures {
-            let rec_param = complete_signature
-                .get((complete_signature.len() as usize - 2)..(complete_signature.len() as usize))
-                .unwrap();
-            let mut mut_sig_str = complete_signature.clone();
-            mut_sig_str.pop();
-            mut_sig_str.pop();
-
-            let rec_dec = hex::decode(rec_param).unwrap();
-            let rec_norm = rec_dec.first().unwrap() - 27;
-            let r_s = hex::decode(mut_sig_str).unwrap();
-
-            let id = match rec_norm {
-                0 => RecoveryId::new(false, false),
-                1 => RecoveryId::new(true, false),
-                _ => todo!(),
-            };
+    /// Returns the Ethereum-style hash of the serialized claim, as signed by the witnesses.
+    pub fn message_hash(&self) -> Vec<u8> {
+        keccak256_eth(self.claim.serialise().as_str())
+    }
 
-            let signature = Signature::from_bytes(r_s.as_slice().into()).unwrap();
-            let message_digest = Identity256::new().chain(&message_hash);
+    /// Recovers the addresses of the signers from the signed claim, or `None` if any
+    /// signature is malformed.
+    pub fn recover_signers_of_signed_claim(self) -> Option<Vec<Vec<u8>>> {
+        let message_hash = self.message_hash();
+        self.signatures
+            .iter()
+            .map(|complete_signature| recover_signer(&message_hash, complete_signature))
+            .collect()
+    }
+}
 
-            // Recover the public key
-            let verkey = VerifyingKey::recover_from_digest(message_digest, &signature, id).unwrap();
-            let key: Vec<u8> = verkey.to_encoded_point(false).as_bytes().into();
-            let hasher = Keccak256::new_with_prefix(&key[1..]);
+/// Recovers the address of the signer of a message hash from a hex-encoded `r || s || v`
+/// signature, or `None` if the signature is malformed.
+///
+/// Recovery goes through the `secp256k1_ecdsa_recover` host function. Signatures whose `r || s`
+/// the host refuses to parse are retried with the in-runtime `k256` implementation, which
+/// rejects most of them before any recovery is attempted.
+pub fn recover_signer(message_hash: &[u8], complete_signature: &str) -> Option<Vec<u8>> {
+    let mut signature = [0_u8; 65];
+    hex::decode_to_slice(complete_signature, &mut signature).ok()?;
+    // Witnesses sign with Ethereum-style recovery ids
+    if !matches!(signature[64], 27 | 28) {
+        return None
+    }
+    let message: [u8; 32] = message_hash.try_into().ok()?;
 
-            let hash = hasher.finalize().to_vec();
-            let address_bytes = hash.get(12..).unwrap();
-            let dec_public_key = hex::decode(hex::encode(address_bytes)).unwrap();
-            expected.push(dec_public_key);
-        }
-        expected
+    match sp_io::crypto::secp256k1_ecdsa_recover(&signature, &message) {
+        Ok(public_key) => Some(sp_io::hashing::keccak_256(&public_key)[12..].to_vec()),
+        Err(EcdsaVerifyError::BadRS) => recover_signer_in_runtime(message_hash, complete_signature),
+        Err(_) => None,
     }
 }
 
+/// Recovers the signer of a message hash with `k256`, as a fallback for `recover_signer`.
+fn recover_signer_in_runtime(message_hash: &[u8], complete_signature: &str) -> Option<Vec<u8>> {
+    let rec_param = complete_signature.get(complete_signature.len().checked_sub(2)?..)?;
+    let mut mut_sig_str = String::from(complete_signature);
+    mut_sig_str.pop();
+    mut_sig_str.pop();
+
+    let rec_dec = hex::decode(rec_param).ok()?;
+    let rec_norm = rec_dec.first()?.checked_sub(27)?;
+    let r_s = hex::decode(mut_sig_str).ok()?;
+
+    let id = match rec_norm {
+        0 => RecoveryId::new(false, false),
+        1 => RecoveryId::new(true, false),
+        _ => return None,
+    };
+
+    let signature = Signature::from_slice(r_s.as_slice()).ok()?;
+    let message_digest = Identity256::new().chain(message_hash);
+
+    // Recover the public key
+    let verkey = VerifyingKey::recover_from_digest(message_digest, &signature, id).ok()?;
+    let key: Vec<u8> = verkey.to_encoded_point(false).as_bytes().into();
+    let hash = sp_io::hashing::keccak_256(&key[1..]);
+    Some(hash[12..].to_vec())
+}
+
+/// How far a proof check got, used to weigh the work it actually did.
+#[derive(Clone, Copy, Default, PartialEq, Eq, Debug)]
+pub struct ProofCheckProgress {
+    pub witnesses: u32,  // Size of the witness set the proof was checked against
+    pub signatures: u32, // Number of signatures recovered
+}
+
 /// Represents a proof consisting of claim information and a signed claim.
 #[derive(Encode, Decode, Eq, PartialEq, Clone, scale_info::TypeInfo, Debug)]
 pub struct Proof {
@@ -181,9 +264,78 @@ pub struct Proof {
     pub signedClaim: SignedClaim, // The signed claim
 }
 
-/// Fetches the witnesses for a claim based on the epoch, identifier, and timestamp.
+/// How `verify_proofs` handles proofs of a batch that fail verification.
+#[derive(Encode, Decode, Eq, PartialEq, Clone, Copy, scale_info::TypeInfo, MaxEncodedLen, Debug)]
+pub enum BatchMode {
+    AllOrNothing, // The whole batch fails on the first invalid proof
+    BestEffort,   // Invalid proofs are reported and skipped
+}
+
+/// Proofs a pause of verification applies to.
+#[derive(Encode, Decode, Eq, PartialEq, Clone, Copy, scale_info::TypeInfo, MaxEncodedLen, Debug)]
+pub enum PauseScope {
+    All,                    // Every proof
+    Epoch(u64),             // Proofs verified in an epoch
+    Provider(ProviderHash), // Proofs of claims made with a provider
+}
+
+/// Proof submissions of an account counted against its rate limit.
+#[derive(Encode, Decode, Eq, PartialEq, Clone, Default, scale_info::TypeInfo, MaxEncodedLen, Debug)]
+pub struct ProofAttempts<BlockNumber> {
+    pub window_start: BlockNumber, // Block the current window started in
+    pub attempts: u32,             // Attempts counted in the current window
+    pub failures: u32,             // Consecutive failed submissions
+}
+
+/// Record of a verified claim, stored with a deposit reserved from its submitter.
+#[derive(Encode, Decode, Eq, PartialEq, Clone, scale_info::TypeInfo, MaxEncodedLen, Debug)]
+pub struct VerifiedClaim<AccountId, BlockNumber, Hash, Balance> {
+    pub submitter: AccountId,                // Account that submitted the proof
+    pub owner: BoundedVec<u8, ConstU32<64>>, // Owner of the claim
+    pub epoch: u64,                          // Epoch the proof was verified in
+    pub block: BlockNumber,                  // Block in which the proof was verified
+    pub provider_hash: Option<ProviderHash>, // Provider hash declared in the claim context
+    pub parameters_hash: Hash,               // Hash of the parameters extracted from the response
+    pub deposit: Balance,                    // Deposit reserved from the submitter
+}
+
+/// Record of the first verification of a claim, kept whether or not the claim is stored with a
+/// deposit, so that it can be revoked by its owner or the witnesses that attested it. The
+/// witnesses are not stored: they are selected again from the epoch the claim was verified in.
+#[derive(Encode, Decode, Eq, PartialEq, Clone, scale_info::TypeInfo, MaxEncodedLen, Debug)]
+pub struct ClaimAttestation<VerifiedClaim> {
+    pub claim: VerifiedClaim, // Claim as first verified
+    pub timestamp: u64,       // Timestamp when the claim was made, selecting its witnesses
+}
+
+/// Proof accepted optimistically with a bond reserved from its submitter, verified in full only
+/// if challenged before `expires_at`.
+#[derive(Encode, Decode, Eq, PartialEq, Clone, scale_info::TypeInfo, MaxEncodedLen, Debug)]
+pub struct PendingProof<AccountId, BlockNumber, Balance, EncodedProof> {
+    pub submitter: AccountId,    // Account that submitted the proof
+    pub proof: EncodedProof,     // SCALE encoding of the submitted proof
+    pub epoch: u64,              // Epoch the proof was submitted in
+    pub bond: Balance,           // Bond reserved from the submitter
+    pub expires_at: BlockNumber, // Block in which the proof is finalized unless challenged
+    pub signatures: u32,         // Number of witness signatures of the proof
+}
+
+impl<AccountId, BlockNumber, Balance, EncodedProof: AsRef<[u8]>>
+    PendingProof<AccountId, BlockNumber, Balance, EncodedProof>
+{
+    /// Decodes the submitted proof, failing only if its encoding is corrupted in storage.
+    pub fn decode_proof(&self) -> Result<Proof, DispatchError> {
+        Proof::decode(&mut self.proof.as_ref()).map_err(|_| DispatchError::Corruption)
+    }
+}
+
+/// Fetches the witnesses for a claim based on the epoch, identifier, and timestamp, or none if
+/// the epoch has no witnesses.
 pub fn fetch_witness_for_claim(epoch: Epoch, identifier: String, timestamp: u64) -> Vec<Witness> {
     let mut selected_witness = vec![];
+    if epoch.witness.is_empty() {
+        return selected_witness
+    }
 
     // Generate a hash from the identifier, epoch, and other parameters
     let hash_str = format!(
@@ -201,7 +353,7 @@ pub fn fetch_witness_for_claim(epoch: Epoch, identifier: String, timestamp: u64)
     // Select witnesses based on the generated hash
     let witness_left = epoch.witness.len();
     let mut byte_offset = 0;
-    for _ in 0..epoch.minimum_witness_for_claim_creation.into() {
+    for _ in 0..epoch.minimum_witness_for_claim_creation {
         let random_seed = generate_random_seed(hash_result.clone(), byte_offset) as usize;
         let witness_index = random_seed % witness_left;
         if let Some(data) = epoch.witness.get(witness_index) {
@@ -225,9 +377,35 @@ pub fn keccak256_eth(message: &str) -> Vec<u8> {
     let message: &[u8] = message.as_ref();
     let mut eth_message = format!("\x19Ethereum Signed Message:\n{}", message.len()).into_bytes();
     eth_message.extend_from_slice(message);
-    let mut hasher = Keccak256::new();
-    hasher.update(&eth_message);
-    hasher.finalize().to_vec()
+    sp_io::hashing::keccak_256(&eth_message).to_vec()
+}
+
+/// Returns the text signed to revoke the verified claim with `identifier`.
+pub fn revocation_text(identifier: &[u8; 32]) -> String {
+    format!("Revoke Reclaim claim 0x{}", hex::encode(identifier))
+}
+
+/// Returns the text the epoch source signs for the epoch `epoch_id`, of `witness` requiring
+/// `minimum_witness` signatures per claim, on the chain with `genesis_hash`. Binding the id and
+/// the chain keeps a signed epoch from being replayed once rotated out, or on another chain.
+pub fn epoch_text(
+    genesis_hash: &[u8],
+    epoch_id: u64,
+    witness: &[Witness],
+    minimum_witness: u32,
+) -> String {
+    let witnesses = witness
+        .iter()
+        .map(|wit| format!("0x{}@0x{}", hex::encode(wit.address), hex::encode(wit.host)))
+        .collect::<Vec<_>>()
+        .join(",");
+    format!(
+        "Reclaim epoch {} on chain 0x{} requiring {} of {}",
+        epoch_id,
+        hex::encode(genesis_hash),
+        minimum_witness,
+        witnesses
+    )
 }
 
 #[frame_support::pallet]
@@ -235,17 +413,134 @@ pub mod pallet {
     use crate::weights::WeightInfo;
     use super::*;
     use frame_support::pallet_prelude::{DispatchResult, StorageMap, *};
-    use frame_system::pallet_prelude::*;
+    use frame_system::{
+        offchain::{AppCrypto, CreateSignedTransaction, SendSignedTransaction, Signer, SigningTypes},
+        pallet_prelude::*,
+    };
+    use frame_support::{
+        storage::with_storage_layer,
+        traits::{Currency, Imbalance, OnUnbalanced, ReservableCurrency},
+    };
+    use sp_runtime::{traits::{Hash, One, Saturating, Zero}, Perbill, RuntimeAppPublic};
+    #[cfg(any(feature = "try-runtime", test))]
+    use sp_runtime::TryRuntimeError;
+
+    /// Application public key of the offchain worker signer.
+    type AppPublicOf<T> = <<T as Config>::AuthorityId as AppCrypto<
+        <T as SigningTypes>::Public,
+        <T as SigningTypes>::Signature,
+    >>::RuntimeAppPublic;
+
+    /// Generic public key of the offchain worker signer.
+    type GenericPublicOf<T> = <<T as Config>::AuthorityId as AppCrypto<
+        <T as SigningTypes>::Public,
+        <T as SigningTypes>::Signature,
+    >>::GenericPublic;
+
+    /// Balance of the currency storage deposits are reserved in.
+    pub type BalanceOf<T> =
+        <<T as Config>::Currency as Currency<<T as frame_system::Config>::AccountId>>::Balance;
+
+    /// Negative imbalance of the currency bonds are slashed in.
+    pub type NegativeImbalanceOf<T> = <<T as Config>::Currency as Currency<
+        <T as frame_system::Config>::AccountId,
+    >>::NegativeImbalance;
+
+    /// Pending optimistic proof of the runtime.
+    pub type PendingProofOf<T> = PendingProof<
+        <T as frame_system::Config>::AccountId,
+        BlockNumberFor<T>,
+        BalanceOf<T>,
+        BoundedVec<u8, <T as Config>::MaxOptimisticProofSize>,
+    >;
+
+    /// Verified claim of the runtime.
+    pub type VerifiedClaimOf<T> = VerifiedClaim<
+        <T as frame_system::Config>::AccountId,
+        BlockNumberFor<T>,
+        <T as frame_system::Config>::Hash,
+        BalanceOf<T>,
+    >;
+
+    /// Attestation of a verified claim of the runtime.
+    pub type ClaimAttestationOf<T> = ClaimAttestation<VerifiedClaimOf<T>>;
+
+    /// The in-code storage version.
+    const STORAGE_VERSION: StorageVersion = StorageVersion::new(2);
 
     #[pallet::pallet]
+    #[pallet::storage_version(STORAGE_VERSION)]
     pub struct Pallet<T>(_);
 
     /// Configuration trait for the Reclaim pallet.
     #[pallet::config]
-    pub trait Config: frame_system::Config + timestamp::Config {
+    pub trait Config:
+        CreateSignedTransaction<Call<Self>> + frame_system::Config + timestamp::Config
+    {
         type RuntimeEvent: From<Event<Self>> + IsType<<Self as frame_system::Config>::RuntimeEvent>;
         type Signature: Verify<Signer = Self::PublicKey> + Encode + Decode + Parameter;
         type PublicKey: IdentifyAccount<AccountId = Self::PublicKey> + Encode + Decode + Parameter;
+        /// Scheme witnesses sign claims with, e.g. `schemes::EthereumWitnesses`.
+        type WitnessSignatureScheme: WitnessSignatureScheme<
+            <Self as pallet::Config>::Signature,
+            Self::PublicKey,
+        >;
+        /// Key used by the offchain worker to sign epoch sync transactions.
+        type AuthorityId: AppCrypto<<Self as SigningTypes>::Public, <Self as SigningTypes>::Signature>;
+        /// Default URL the offchain worker fetches the current epoch from.
+        type EpochSourceUrl: Get<&'static str>;
+        /// Addresses of the keys the epoch source signs epochs with. Epochs signed by any other
+        /// key are not synced.
+        type EpochSourceSigners: Get<Vec<[u8; 20]>>;
+        /// Number of blocks between two epoch syncs by the offchain worker.
+        #[pallet::constant]
+        type EpochSyncInterval: Get<BlockNumberFor<Self>>;
+        /// Maximum number of proofs verified by a single `verify_proofs` call.
+        #[pallet::constant]
+        type MaxBatch: Get<u32>;
+        /// Origin allowed to initialize the contract and choose its owner.
+        type InitOrigin: EnsureOrigin<Self::RuntimeOrigin>;
+        /// Origin allowed to pause and unpause proof verification.
+        type AdminOrigin: EnsureOrigin<Self::RuntimeOrigin>;
+        /// Number of blocks the proof submissions of an account are counted over. Zero disables
+        /// the rate limit.
+        #[pallet::constant]
+        type ProofAttemptWindow: Get<BlockNumberFor<Self>>;
+        /// Maximum number of proof submissions of an account per window.
+        #[pallet::constant]
+        type MaxProofAttempts: Get<u32>;
+        /// Currency the storage deposits of verified claims are reserved in.
+        type Currency: ReservableCurrency<Self::AccountId>;
+        /// Deposit reserved from the submitter of each stored verified claim. `None` disables
+        /// storing verified claims.
+        type VerifiedClaimDeposit: Get<Option<BalanceOf<Self>>>;
+        /// Hook called when a proof is verified, e.g. a tuple of pallets.
+        type OnProofVerified: OnProofVerified<Self::AccountId, VerifiedClaimOf<Self>>;
+        /// Hook called when a verified claim is revoked, e.g. a tuple of pallets.
+        type OnClaimRevoked: OnClaimRevoked<VerifiedClaimOf<Self>>;
+        /// Bond reserved from the submitter of each optimistic proof. `None` disables optimistic
+        /// verification.
+        type OptimisticBond: Get<Option<BalanceOf<Self>>>;
+        /// Bond reserved on top of `OptimisticBond` per byte of the encoded proof.
+        #[pallet::constant]
+        type OptimisticBondPerByte: Get<BalanceOf<Self>>;
+        /// Number of blocks an optimistic proof can be challenged for. Proofs stay pending for
+        /// at least one block.
+        #[pallet::constant]
+        type ChallengePeriod: Get<BlockNumberFor<Self>>;
+        /// Maximum number of optimistic proofs finalized in a single block.
+        #[pallet::constant]
+        type MaxPendingPerBlock: Get<u32>;
+        /// Maximum size of the encoded proof of an optimistic submission, bounding its storage
+        /// and the weight of challenging it.
+        #[pallet::constant]
+        type MaxOptimisticProofSize: Get<u32>;
+        /// Handler of the bonds slashed from the submitters of invalid optimistic proofs, less the
+        /// reward of their challenger.
+        type Slash: OnUnbalanced<NegativeImbalanceOf<Self>>;
+        /// Share of a slashed optimistic bond paid to the challenger that showed the proof invalid.
+        #[pallet::constant]
+        type ChallengerReward: Get<Perbill>;
         type WeightInfo: WeightInfo;
     }
 
@@ -257,15 +552,119 @@ pub mod pallet {
     /// Storage for epochs.
     #[pallet::storage]
     #[pallet::getter(fn epochs)]
-    pub(super) type Epochs<T: Config> = StorageMap<_, Blake2_128Concat, u64, Epoch, ValueQuery>;
+    pub(super) type Epochs<T: Config> = StorageMap<_, Blake2_128Concat, u64, Epoch, OptionQuery>;
+
+    /// Storage for the accounts allowed to sync epochs from the epoch source.
+    #[pallet::storage]
+    #[pallet::getter(fn epoch_syncers)]
+    pub type EpochSyncers<T: Config> =
+        StorageValue<_, BoundedVec<T::AccountId, ConstU32<10>>, ValueQuery>;
+
+    /// Storage for the scopes in which proof verification is paused.
+    #[pallet::storage]
+    pub type Paused<T: Config> = StorageMap<_, Blake2_128Concat, PauseScope, (), OptionQuery>;
+
+    /// Storage for the witness addresses whose signatures are refused, with the block they were
+    /// revoked in.
+    #[pallet::storage]
+    #[pallet::getter(fn revoked_witnesses)]
+    pub type RevokedWitnesses<T: Config> =
+        StorageMap<_, Blake2_128Concat, [u8; 20], BlockNumberFor<T>, OptionQuery>;
+
+    /// Storage for the verified claims, by claim identifier.
+    #[pallet::storage]
+    #[pallet::getter(fn verified_claims)]
+    pub type VerifiedClaims<T: Config> =
+        StorageMap<_, Blake2_128Concat, [u8; 32], VerifiedClaimOf<T>, OptionQuery>;
+
+    /// Storage for the attestations of the claims verified so far, by claim identifier. Unlike
+    /// `VerifiedClaims`, they can't be removed by the submitter, and take no deposit as their
+    /// size is fixed.
+    #[pallet::storage]
+    #[pallet::getter(fn claim_attestations)]
+    pub type ClaimAttestations<T: Config> =
+        StorageMap<_, Blake2_128Concat, [u8; 32], ClaimAttestationOf<T>, OptionQuery>;
+
+    /// Storage for the identifiers of the revoked claims, with the block they were revoked in.
+    #[pallet::storage]
+    #[pallet::getter(fn revoked_claims)]
+    pub type RevokedClaims<T: Config> =
+        StorageMap<_, Blake2_128Concat, [u8; 32], BlockNumberFor<T>, OptionQuery>;
+
+    /// Storage for the optimistic proofs awaiting finalization, by proof id.
+    #[pallet::storage]
+    #[pallet::getter(fn pending_proofs)]
+    pub type PendingProofs<T: Config> =
+        StorageMap<_, Twox64Concat, u64, PendingProofOf<T>, OptionQuery>;
+
+    /// Storage for the ids of the optimistic proofs finalized in each block.
+    #[pallet::storage]
+    pub type PendingExpiries<T: Config> = StorageMap<
+        _,
+        Twox64Concat,
+        BlockNumberFor<T>,
+        BoundedVec<u64, T::MaxPendingPerBlock>,
+        ValueQuery,
+    >;
+
+    /// Storage for the id of the next optimistic proof.
+    #[pallet::storage]
+    pub type NextPendingProofId<T: Config> = StorageValue<_, u64, ValueQuery>;
+
+    /// Storage for the proof submissions of each account in its current rate limit window.
+    #[pallet::storage]
+    #[pallet::getter(fn proof_attempts)]
+    pub type AccountProofAttempts<T: Config> = StorageMap<
+        _,
+        Blake2_128Concat,
+        T::AccountId,
+        ProofAttempts<BlockNumberFor<T>>,
+        OptionQuery,
+    >;
 
     /// Events emitted by the Reclaim pallet.
     #[pallet::event]
     #[pallet::generate_deposit(pub(super) fn deposit_event)]
     pub enum Event<T: Config> {
         ContractInitialized { owner: T::AccountId }, // Event emitted when the contract is initialized
-        ProofVerified { epoch_id: u64 },             // Event emitted when a proof is verified
-        EpochAdded { epoch_id: u64 },                // Event emitted when a new epoch is added
+        ProofVerified {
+            who: T::AccountId,                   // Account that submitted the proof
+            identifier: String,                  // Identifier of the verified claim
+            owner: String,                       // Owner of the verified claim
+            provider_hash: Option<ProviderHash>, // Provider hash declared in the claim context
+            epoch_id: u64,                       // Epoch the proof was verified in
+            timestamp: u64,                      // Timestamp when the claim was made
+        }, // Event emitted when a proof is verified
+        EpochAdded {
+            epoch_id: u64,                                   // Identifier of the new epoch
+            witnesses: BoundedVec<Witness, ConstU32<100>>,   // Witnesses of the new epoch
+            minimum: u32,                                    // Minimum witnesses needed for claim creation
+            start: u64,                                      // Start timestamp
+            end: u64,                                        // End timestamp
+        }, // Event emitted when a new epoch is added
+        WitnessesChanged { epoch_id: u64, added: Vec<[u8; 20]>, removed: Vec<[u8; 20]> }, // Event emitted when an epoch changes the witness addresses
+        OwnershipTransferred { old_owner: T::AccountId, new_owner: T::AccountId }, // Event emitted when the owner changes
+        VerificationPaused { scope: PauseScope },   // Event emitted when proof verification is paused
+        VerificationUnpaused { scope: PauseScope }, // Event emitted when proof verification is resumed
+        WitnessRevoked { address: [u8; 20] },       // Event emitted when a witness is revoked
+        VerifiedClaimRemoved { identifier: [u8; 32] }, // Event emitted when a verified claim is removed
+        ClaimRevoked { identifier: [u8; 32] },         // Event emitted when a verified claim is revoked
+        OptimisticProofSubmitted {
+            proof_id: u64,                 // Identifier of the pending proof
+            who: T::AccountId,             // Account that submitted the proof
+            expires_at: BlockNumberFor<T>, // Block in which the proof is finalized unless challenged
+        }, // Event emitted when a proof is accepted pending its challenge period
+        OptimisticProofSlashed {
+            proof_id: u64,            // Identifier of the pending proof
+            challenger: T::AccountId, // Account that challenged the proof
+            error: DispatchError,     // Reason the proof is invalid
+        }, // Event emitted when a challenged proof is invalid and its bond is slashed
+        OptimisticProofFinalized { proof_id: u64 }, // Event emitted when a pending proof is verified or its challenge period ends
+        OptimisticProofDropped { proof_id: u64, error: DispatchError }, // Event emitted when a proof can't be recorded and its bond is released
+        EpochSyncersSet { syncers: BoundedVec<T::AccountId, ConstU32<10>> }, // Event emitted when the epoch syncers change
+        ProofRejected { index: u32, error: DispatchError }, // Event emitted when a proof of a best-effort batch fails
+        BatchVerified { verified: u32, rejected: u32 }, // Event emitted when a batch of proofs is processed
+        OptimisticProofDeferred { proof_id: u64 }, // Event emitted when an unchallenged proof is left pending and rescheduled while paused
     }
 
     /// Errors for the Reclaim pallet.
@@ -276,34 +675,96 @@ pub mod pallet {
         HashMismatch,        // Hash verification failed
         LengthMismatch,      // Length mismatch in data
         SignatureMismatch,   // Signature verification failed
+        OnlyEpochSyncer,     // Action restricted to the owner or an epoch syncer
+        NotInitialized,      // Contract is not initialized
+        InvalidSignature,    // A signature of the claim is malformed
+        InvalidMinimumWitness, // Minimum witnesses is zero or exceeds the witness set
+        EmptyWitnessSet,     // Epoch has no witnesses
+        DuplicateWitness,    // Epoch lists a witness address more than once
+        EpochNotFound,       // Current epoch is not stored
+        Paused,              // Proof verification is paused
+        AlreadyPaused,       // Proof verification is already paused in the scope
+        NotPaused,           // Proof verification is not paused in the scope
+        RevokedWitness,      // A signature of the claim is from a revoked witness
+        AlreadyRevoked,      // Witness is already revoked
+        RateLimited,         // Account has no proof submissions left in the current window
+        InvalidClaimIdentifier, // Claim identifier is not a 32-byte hex string
+        InvalidClaimOwner,   // Claim owner is not a hex string of at most 64 bytes
+        ClaimNotFound,       // Verified claim is not stored, or was never verified
+        NotClaimSubmitter,   // Action restricted to the submitter of the verified claim
+        ClaimAlreadyRevoked, // Verified claim is already revoked
+        UnauthorizedRevocation, // Signed neither by the owner nor by all attesting witnesses
+        RevokedClaim,        // Claim has been revoked
+        OptimisticDisabled,  // Optimistic verification is disabled
+        ProofTooLarge,       // Encoded proof exceeds the maximum size of optimistic proofs
+        TooManyPendingProofs, // Block the proof would be finalized in has no room left
+        PendingProofNotFound, // Optimistic proof is not pending
+        InvalidEpochSignature, // Epoch is not signed by an epoch source signer
+        EpochUnchanged,      // Epoch has the witnesses and minimum of the current epoch
+        SignatureCountTooLow, // Challenge is weighed for fewer signatures than the proof has
+    }
+
+    #[pallet::hooks]
+    impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+        /// Finalizes the optimistic proofs whose challenge period ends in this block.
+        fn on_initialize(now: BlockNumberFor<T>) -> Weight {
+            let expiring = <PendingExpiries<T>>::take(now);
+            let count = expiring.len() as u32;
+            for proof_id in expiring {
+                Self::finalize_pending_proof(proof_id);
+            }
+            <T as pallet::Config>::WeightInfo::finalize_pending_proofs(count).saturating_add(
+                T::OnProofVerified::on_proof_verified_weight().saturating_mul(count.into()),
+            )
+        }
+
+        /// Periodically syncs the current epoch from the epoch source.
+        fn offchain_worker(block_number: BlockNumberFor<T>) {
+            let interval = T::EpochSyncInterval::get();
+            if interval.is_zero() || !(block_number % interval).is_zero() {
+                return
+            }
+            if let Err(e) = Self::sync_epoch_from_source() {
+                log::warn!(target: "runtime::reclaim", "Epoch sync failed: {}", e);
+            }
+        }
+
+        #[cfg(feature = "try-runtime")]
+        fn try_state(_n: BlockNumberFor<T>) -> Result<(), TryRuntimeError> {
+            Self::do_try_state()
+        }
     }
 
     #[pallet::call]
     impl<T: Config> Pallet<T> {
-        /// Initializes the Reclaim contract.
+        /// Initializes the Reclaim contract with `owner` as its owner. Only callable by
+        /// `InitOrigin`.
         #[pallet::call_index(0)]
         #[pallet::weight(<T as pallet::Config>::WeightInfo::init())]
-        pub fn init(origin: OriginFor<T>) -> DispatchResult {
-            let who = ensure_signed(origin)?;
+        pub fn init(origin: OriginFor<T>, owner: T::AccountId) -> DispatchResult {
+            T::InitOrigin::ensure_origin(origin)?;
             ensure!(!<PReclaimConfig<T>>::exists(), Error::<T>::AlreadyInitialized);
-            let reclaim_config = ReclaimConfig { owner: who.clone(), current_epoch: 0_u64 };
+            let reclaim_config = ReclaimConfig { owner: owner.clone(), current_epoch: 0_u64 };
             <PReclaimConfig<T>>::put(reclaim_config);
-            Self::deposit_event(Event::ContractInitialized { owner: who });
+            Self::deposit_event(Event::ContractInitialized { owner });
             Ok(())
         }
 
         /// Verifies a proof.
         #[pallet::call_index(1)]
-        #[pallet::weight(<T as pallet::Config>::WeightInfo::verify_proof())]
+        #[pallet::weight(<T as pallet::Config>::WeightInfo::verify_proof(
+            signed_claim.signatures.len() as u32,
+            MAX_WITNESSES,
+            claim_info.size(),
+        ).saturating_add(T::OnProofVerified::on_proof_verified_weight()))]
         pub fn verify_proof(
             origin: OriginFor<T>,
             claim_info: ClaimInfo,
             signed_claim: SignedClaim,
-        ) -> DispatchResult {
-            let _who = ensure_signed(origin)?;
+        ) -> DispatchResultWithPostInfo {
+            let who = ensure_signed(origin)?;
             let proof = Proof { claimInfo: claim_info, signedClaim: signed_claim };
-            <Self as ReclaimVerifier<Proof>>::verify_proof(&proof)?;
-            Ok(())
+            <Self as ReclaimVerifier<T::AccountId, Proof>>::verify_proof(&who, &proof)
         }
 
         /// Adds a new epoch.
@@ -312,12 +773,331 @@ pub mod pallet {
         pub fn add_epoch(
             origin: OriginFor<T>,
             witness: BoundedVec<Witness, ConstU32<100>>,
-            minimum_witness: u128,
+            minimum_witness: u32,
         ) -> DispatchResult {
             let who = ensure_signed(origin)?;
-            let config = <PReclaimConfig<T>>::get().unwrap();
-            let owner = config.owner;
-            ensure!(who == owner, Error::<T>::OnlyOwner);
+            let config = <PReclaimConfig<T>>::get().ok_or(Error::<T>::NotInitialized)?;
+            ensure!(who == config.owner, Error::<T>::OnlyOwner);
+            Self::do_add_epoch(config, witness, minimum_witness)
+        }
+
+        /// Adds a new epoch fetched from the epoch source by the offchain worker. `signature`
+        /// signs the epoch with the witness signature scheme, by one of `EpochSourceSigners`,
+        /// for the id of the next epoch on this chain. Fails if the epoch has the witnesses and
+        /// minimum of the current epoch.
+        #[pallet::call_index(3)]
+        #[pallet::weight(<T as pallet::Config>::WeightInfo::sync_epoch())]
+        pub fn sync_epoch(
+            origin: OriginFor<T>,
+            witness: BoundedVec<Witness, ConstU32<100>>,
+            minimum_witness: u32,
+            signature: String,
+        ) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+            let config = <PReclaimConfig<T>>::get().ok_or(Error::<T>::NotInitialized)?;
+            ensure!(
+                who == config.owner || <EpochSyncers<T>>::get().contains(&who),
+                Error::<T>::OnlyEpochSyncer
+            );
+            Self::ensure_signed_by_epoch_source(
+                config.current_epoch + 1_u64,
+                &witness,
+                minimum_witness,
+                &signature,
+            )?;
+            ensure!(
+                !Self::is_current_epoch(config.current_epoch, &witness, minimum_witness),
+                Error::<T>::EpochUnchanged
+            );
+            Self::do_add_epoch(config, witness, minimum_witness)
+        }
+
+        /// Sets the accounts allowed to sync epochs from the epoch source.
+        #[pallet::call_index(4)]
+        #[pallet::weight(<T as pallet::Config>::WeightInfo::set_epoch_syncers())]
+        pub fn set_epoch_syncers(
+            origin: OriginFor<T>,
+            syncers: BoundedVec<T::AccountId, ConstU32<10>>,
+        ) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+            let config = <PReclaimConfig<T>>::get().ok_or(Error::<T>::NotInitialized)?;
+            ensure!(who == config.owner, Error::<T>::OnlyOwner);
+            <EpochSyncers<T>>::put(syncers.clone());
+            Self::deposit_event(Event::EpochSyncersSet { syncers });
+            Ok(())
+        }
+
+        /// Verifies a batch of proofs against the current epoch, read once for the whole batch.
+        ///
+        /// In `AllOrNothing` mode the call fails on the first invalid proof and nothing is
+        /// recorded; in `BestEffort` mode invalid proofs are reported with `ProofRejected`.
+        /// Each proof counts as a submission against the rate limit of the sender.
+        #[pallet::call_index(5)]
+        #[pallet::weight(Pallet::<T>::verify_proofs_weight(proofs))]
+        pub fn verify_proofs(
+            origin: OriginFor<T>,
+            proofs: BoundedVec<Proof, T::MaxBatch>,
+            mode: BatchMode,
+        ) -> DispatchResultWithPostInfo {
+            let who = ensure_signed(origin)?;
+            Self::ensure_within_rate_limit(&who)?;
+            let current_epoch = Self::current_epoch()?;
+            let mut actual_weight = Weight::zero();
+            let mut verified = 0_u32;
+
+            for (index, proof) in proofs.iter().enumerate() {
+                let mut progress = ProofCheckProgress::default();
+                let result = Self::ensure_within_rate_limit(&who)
+                    .and_then(|()| Self::check_proof_in_epoch(proof, &current_epoch, &mut progress))
+                    .map_err(DispatchError::from)
+                    .and_then(|()| {
+                        Self::record_verified_claim(&who, proof, &current_epoch, true)
+                    });
+                actual_weight.saturating_accrue(Self::proof_check_weight(proof, progress));
+                match (result, mode) {
+                    (Ok(()), _) => {
+                        actual_weight
+                            .saturating_accrue(T::OnProofVerified::on_proof_verified_weight());
+                        verified += 1;
+                        Self::note_proof_attempt(&who, true);
+                        Self::deposit_proof_verified(&who, proof, current_epoch.id);
+                    },
+                    (Err(error), BatchMode::AllOrNothing) =>
+                        return Err(error.with_weight(actual_weight)),
+                    (Err(error), BatchMode::BestEffort) => {
+                        // Proofs refused by the rate limit were not checked
+                        if error != Error::<T>::RateLimited.into() {
+                            Self::note_proof_attempt(&who, false);
+                        }
+                        Self::deposit_event(Event::ProofRejected { index: index as u32, error });
+                    },
+                }
+            }
+
+            Self::deposit_event(Event::BatchVerified {
+                verified,
+                rejected: proofs.len() as u32 - verified,
+            });
+            Ok(Some(actual_weight).into())
+        }
+
+        /// Transfers the ownership of the Reclaim contract. Only callable by the owner.
+        #[pallet::call_index(6)]
+        #[pallet::weight(<T as pallet::Config>::WeightInfo::transfer_ownership())]
+        pub fn transfer_ownership(origin: OriginFor<T>, new_owner: T::AccountId) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+            let config = <PReclaimConfig<T>>::get().ok_or(Error::<T>::NotInitialized)?;
+            ensure!(who == config.owner, Error::<T>::OnlyOwner);
+            <PReclaimConfig<T>>::put(ReclaimConfig {
+                owner: new_owner.clone(),
+                current_epoch: config.current_epoch,
+            });
+            Self::deposit_event(Event::OwnershipTransferred { old_owner: who, new_owner });
+            Ok(())
+        }
+
+        /// Pauses proof verification in `scope`. Only callable by `AdminOrigin`.
+        #[pallet::call_index(7)]
+        #[pallet::weight(<T as pallet::Config>::WeightInfo::pause())]
+        pub fn pause(origin: OriginFor<T>, scope: PauseScope) -> DispatchResult {
+            T::AdminOrigin::ensure_origin(origin)?;
+            ensure!(!<Paused<T>>::contains_key(scope), Error::<T>::AlreadyPaused);
+            <Paused<T>>::insert(scope, ());
+            Self::deposit_event(Event::VerificationPaused { scope });
+            Ok(())
+        }
+
+        /// Resumes proof verification in `scope`. Only callable by `AdminOrigin`.
+        #[pallet::call_index(8)]
+        #[pallet::weight(<T as pallet::Config>::WeightInfo::unpause())]
+        pub fn unpause(origin: OriginFor<T>, scope: PauseScope) -> DispatchResult {
+            T::AdminOrigin::ensure_origin(origin)?;
+            ensure!(<Paused<T>>::contains_key(scope), Error::<T>::NotPaused);
+            <Paused<T>>::remove(scope);
+            Self::deposit_event(Event::VerificationUnpaused { scope });
+            Ok(())
+        }
+
+        /// Revokes a witness, refusing every signature it makes from now on, in any epoch.
+        /// Only callable by `AdminOrigin`.
+        #[pallet::call_index(9)]
+        #[pallet::weight(<T as pallet::Config>::WeightInfo::revoke_witness())]
+        pub fn revoke_witness(origin: OriginFor<T>, address: [u8; 20]) -> DispatchResult {
+            T::AdminOrigin::ensure_origin(origin)?;
+            ensure!(!Self::is_witness_revoked(&address), Error::<T>::AlreadyRevoked);
+            let now = frame_system::Pallet::<T>::block_number();
+            <RevokedWitnesses<T>>::insert(address, now);
+            Self::deposit_event(Event::WitnessRevoked { address });
+            Ok(())
+        }
+
+        /// Removes a verified claim, unreserving its deposit. Only callable by the submitter of
+        /// the claim.
+        #[pallet::call_index(10)]
+        #[pallet::weight(<T as pallet::Config>::WeightInfo::remove_verified_claim())]
+        pub fn remove_verified_claim(origin: OriginFor<T>, identifier: [u8; 32]) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+            let claim = <VerifiedClaims<T>>::get(identifier).ok_or(Error::<T>::ClaimNotFound)?;
+            ensure!(claim.submitter == who, Error::<T>::NotClaimSubmitter);
+            <VerifiedClaims<T>>::remove(identifier);
+            T::Currency::unreserve(&who, claim.deposit);
+            Self::deposit_event(Event::VerifiedClaimRemoved { identifier });
+            Ok(())
+        }
+
+        /// Revokes a verified claim, refusing any new proof of it. `signatures` sign
+        /// `revocation_text(identifier)`: either one Ethereum-style signature of the claim
+        /// owner, or signatures of every unrevoked witness that attested the claim, with the
+        /// witness signature scheme.
+        #[pallet::call_index(11)]
+        #[pallet::weight(
+            <T as pallet::Config>::WeightInfo::revoke_claim(signatures.len() as u32)
+                .saturating_add(T::OnClaimRevoked::on_claim_revoked_weight())
+        )]
+        pub fn revoke_claim(
+            origin: OriginFor<T>,
+            identifier: [u8; 32],
+            signatures: BoundedVec<String, ConstU32<100>>,
+        ) -> DispatchResult {
+            ensure_signed(origin)?;
+            let attestation =
+                <ClaimAttestations<T>>::get(identifier).ok_or(Error::<T>::ClaimNotFound)?;
+            ensure!(
+                !<RevokedClaims<T>>::contains_key(identifier),
+                Error::<T>::ClaimAlreadyRevoked
+            );
+            ensure!(
+                Self::is_signed_by_owner(&identifier, &attestation.claim, &signatures) ||
+                    Self::is_signed_by_witnesses(&identifier, &attestation, &signatures),
+                Error::<T>::UnauthorizedRevocation
+            );
+            let now = frame_system::Pallet::<T>::block_number();
+            <RevokedClaims<T>>::insert(identifier, now);
+            T::OnClaimRevoked::on_claim_revoked(&identifier, &attestation.claim);
+            Self::deposit_event(Event::ClaimRevoked { identifier });
+            Ok(())
+        }
+
+        /// Submits a proof optimistically, reserving `OptimisticBond` plus `OptimisticBondPerByte`
+        /// for each byte of the encoded proof from the sender. Only the checks needing no signer
+        /// recovery run now: the proof is recorded once its `ChallengePeriod` ends, unless a
+        /// `challenge` shows it is invalid.
+        #[pallet::call_index(12)]
+        #[pallet::weight(<T as pallet::Config>::WeightInfo::submit_optimistic(
+            claim_info.encoded_size().saturating_add(signed_claim.encoded_size()).saturated_into(),
+        ))]
+        pub fn submit_optimistic(
+            origin: OriginFor<T>,
+            claim_info: ClaimInfo,
+            signed_claim: SignedClaim,
+        ) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+            let bond = T::OptimisticBond::get().ok_or(Error::<T>::OptimisticDisabled)?;
+            let proof = Proof { claimInfo: claim_info, signedClaim: signed_claim };
+            let encoded_proof: BoundedVec<u8, T::MaxOptimisticProofSize> =
+                proof.encode().try_into().map_err(|_| Error::<T>::ProofTooLarge)?;
+            let bond = bond.saturating_add(
+                T::OptimisticBondPerByte::get().saturating_mul((encoded_proof.len() as u32).into()),
+            );
+            Self::ensure_within_rate_limit(&who)?;
+            let (current_epoch, _) = Self::check_proof_structure(&proof, &mut Default::default())?;
+
+            let now = frame_system::Pallet::<T>::block_number();
+            let expires_at = now.saturating_add(T::ChallengePeriod::get().max(One::one()));
+            let proof_id = <NextPendingProofId<T>>::get();
+            <PendingExpiries<T>>::try_mutate(expires_at, |expiring| expiring.try_push(proof_id))
+                .map_err(|_| Error::<T>::TooManyPendingProofs)?;
+            T::Currency::reserve(&who, bond)?;
+            <NextPendingProofId<T>>::put(proof_id.saturating_add(1));
+            <PendingProofs<T>>::insert(
+                proof_id,
+                PendingProof {
+                    submitter: who.clone(),
+                    proof: encoded_proof,
+                    epoch: current_epoch.id,
+                    bond,
+                    expires_at,
+                    signatures: proof.signedClaim.signatures.len() as u32,
+                },
+            );
+            Self::note_proof_attempt(&who, true);
+            Self::deposit_event(Event::OptimisticProofSubmitted { proof_id, who, expires_at });
+            Ok(())
+        }
+
+        /// Challenges a pending optimistic proof, fully verifying it in the epoch it was submitted
+        /// in. A proof that was invalid when submitted is dropped and the bond of its submitter
+        /// slashed; a valid one is finalized right away. A proof only invalidated since, e.g. by
+        /// the revocation of a witness, is dropped with its bond released. Fails without slashing
+        /// while verification is paused.
+        ///
+        /// `signatures` is the number of signatures of the pending proof the call is weighed for,
+        /// and must be at least the number stored with it.
+        #[pallet::call_index(13)]
+        #[pallet::weight(Pallet::<T>::challenge_weight(*signatures))]
+        pub fn challenge(
+            origin: OriginFor<T>,
+            proof_id: u64,
+            signatures: u32,
+        ) -> DispatchResultWithPostInfo {
+            let challenger = ensure_signed(origin)?;
+            let pending =
+                <PendingProofs<T>>::get(proof_id).ok_or(Error::<T>::PendingProofNotFound)?;
+            ensure!(pending.signatures <= signatures, Error::<T>::SignatureCountTooLow);
+            let challenge_weight = <T as pallet::Config>::WeightInfo::challenge();
+            let decoded = pending.decode_proof().and_then(|proof| {
+                let epoch = <Epochs<T>>::get(pending.epoch).ok_or(Error::<T>::EpochNotFound)?;
+                Ok((proof, epoch))
+            });
+            let (proof, epoch) = match decoded {
+                Ok(decoded) => decoded,
+                Err(error) => {
+                    Self::drop_pending_proof(proof_id, &pending, error);
+                    return Ok(Some(challenge_weight).into())
+                },
+            };
+            let mut progress = ProofCheckProgress::default();
+            let result = Self::check_proof_in_epoch(&proof, &epoch, &mut progress);
+            let mut actual_weight =
+                challenge_weight.saturating_add(Self::proof_check_weight(&proof, progress));
+
+            match result {
+                Ok(()) => {
+                    Self::finalize_pending_proof(proof_id);
+                    actual_weight.saturating_accrue(T::OnProofVerified::on_proof_verified_weight());
+                },
+                Err(Error::<T>::Paused) =>
+                    return Err(Error::<T>::Paused.with_weight(actual_weight)),
+                Err(error) if !Self::is_submission_error(&error) =>
+                    Self::drop_pending_proof(proof_id, &pending, error.into()),
+                Err(error) => {
+                    <PendingProofs<T>>::remove(proof_id);
+                    let (slashed, _) =
+                        T::Currency::slash_reserved(&pending.submitter, pending.bond);
+                    let reward = T::ChallengerReward::get() * slashed.peek();
+                    let (reward, slashed) = slashed.split(reward);
+                    T::Currency::resolve_creating(&challenger, reward);
+                    T::Slash::on_unbalanced(slashed);
+                    Self::note_proof_attempt(&pending.submitter, false);
+                    Self::deposit_event(Event::OptimisticProofSlashed {
+                        proof_id,
+                        challenger,
+                        error: error.into(),
+                    });
+                },
+            }
+            Ok(Some(actual_weight).into())
+        }
+    }
+
+    impl<T: Config> Pallet<T> {
+        /// Stores a new epoch after the current one and makes it current.
+        fn do_add_epoch(
+            config: ReclaimConfig<T::AccountId>,
+            witness: BoundedVec<Witness, ConstU32<100>>,
+            minimum_witness: u32,
+        ) -> DispatchResult {
+            Self::ensure_valid_epoch(&witness, minimum_witness)?;
             let new_epoch_id = config.current_epoch + 1_u64;
             let now = timestamp::Pallet::<T>::get().saturated_into::<u64>();
             let epoch = Epoch {
@@ -328,58 +1108,686 @@ pub mod pallet {
                 minimum_witness_for_claim_creation: minimum_witness,
             };
 
-            <Epochs<T>>::insert(new_epoch_id, epoch);
-            <PReclaimConfig<T>>::set(Some(ReclaimConfig { owner, current_epoch: new_epoch_id }));
-            Self::deposit_event(Event::EpochAdded { epoch_id: new_epoch_id });
+            // Report the witness addresses that join or leave with the new epoch
+            let previous_addresses: Vec<[u8; 20]> = <Epochs<T>>::get(config.current_epoch)
+                .map(|previous| previous.witness.iter().map(|w| w.address).collect())
+                .unwrap_or_default();
+            let addresses: Vec<[u8; 20]> = epoch.witness.iter().map(|w| w.address).collect();
+            let added: Vec<[u8; 20]> =
+                addresses.iter().filter(|a| !previous_addresses.contains(a)).copied().collect();
+            let removed: Vec<[u8; 20]> =
+                previous_addresses.iter().filter(|a| !addresses.contains(a)).copied().collect();
+
+            <Epochs<T>>::insert(new_epoch_id, epoch.clone());
+            <PReclaimConfig<T>>::set(Some(ReclaimConfig {
+                owner: config.owner,
+                current_epoch: new_epoch_id,
+            }));
+            Self::deposit_event(Event::EpochAdded {
+                epoch_id: new_epoch_id,
+                witnesses: epoch.witness,
+                minimum: epoch.minimum_witness_for_claim_creation,
+                start: epoch.timestamp_start,
+                end: epoch.timestamp_end,
+            });
+            if !added.is_empty() || !removed.is_empty() {
+                Self::deposit_event(Event::WitnessesChanged { epoch_id: new_epoch_id, added, removed });
+            }
+
+            Ok(())
+        }
+
+        /// Ensures an epoch has witnesses, unique addresses and a minimum between one and the
+        /// number of witnesses.
+        fn ensure_valid_epoch(
+            witness: &BoundedVec<Witness, ConstU32<100>>,
+            minimum_witness: u32,
+        ) -> DispatchResult {
+            ensure!(!witness.is_empty(), Error::<T>::EmptyWitnessSet);
+            ensure!(
+                minimum_witness > 0 && minimum_witness as usize <= witness.len(),
+                Error::<T>::InvalidMinimumWitness
+            );
+            let mut addresses: Vec<[u8; 20]> = witness.iter().map(|w| w.address).collect();
+            addresses.sort();
+            addresses.dedup();
+            ensure!(addresses.len() == witness.len(), Error::<T>::DuplicateWitness);
+            Ok(())
+        }
+
+        /// Ensures `signature` signs the epoch `epoch_id` of `witness` requiring
+        /// `minimum_witness` signatures on this chain, by one of `EpochSourceSigners`.
+        fn ensure_signed_by_epoch_source(
+            epoch_id: u64,
+            witness: &[Witness],
+            minimum_witness: u32,
+            signature: &str,
+        ) -> Result<(), Error<T>> {
+            let genesis_hash = frame_system::Pallet::<T>::block_hash(BlockNumberFor::<T>::zero());
+            let message = T::WitnessSignatureScheme::epoch_message(
+                genesis_hash.as_ref(),
+                epoch_id,
+                witness,
+                minimum_witness,
+            );
+            let signer = T::WitnessSignatureScheme::recover_signer(&message, signature)
+                .ok_or(Error::<T>::InvalidEpochSignature)?;
+            ensure!(
+                T::EpochSourceSigners::get().contains(&signer),
+                Error::<T>::InvalidEpochSignature
+            );
+            Ok(())
+        }
+
+        /// Returns whether the epoch `current_epoch` has the witnesses `witness` and requires
+        /// `minimum_witness` signatures.
+        fn is_current_epoch(current_epoch: u64, witness: &[Witness], minimum_witness: u32) -> bool {
+            <Epochs<T>>::get(current_epoch).map_or(false, |epoch| {
+                epoch.witness.as_slice() == witness &&
+                    epoch.minimum_witness_for_claim_creation == minimum_witness
+            })
+        }
+
+        /// Checks the invariants of the Reclaim storage: epochs are stored under contiguous ids
+        /// up to the current one, and each requires between one and all of its witnesses, whose
+        /// addresses are unique.
+        #[cfg(any(feature = "try-runtime", test))]
+        pub fn do_try_state() -> Result<(), TryRuntimeError> {
+            let Some(config) = <PReclaimConfig<T>>::get() else {
+                ensure!(<Epochs<T>>::iter_keys().next().is_none(), "Epochs stored before init");
+                return Ok(())
+            };
+            ensure!(
+                config.current_epoch == 0 || <Epochs<T>>::contains_key(config.current_epoch),
+                "Current epoch is not stored"
+            );
+
+            let mut epoch_ids: Vec<u64> = <Epochs<T>>::iter_keys().collect();
+            epoch_ids.sort();
+            ensure!(
+                epoch_ids.into_iter().eq(1..=config.current_epoch),
+                "Epoch ids are not contiguous up to the current epoch"
+            );
+
+            for (epoch_id, epoch) in <Epochs<T>>::iter() {
+                ensure!(epoch.id == epoch_id, "Epoch is stored under another id");
+                ensure!(
+                    epoch.minimum_witness_for_claim_creation > 0,
+                    "Epoch requires no witness for claim creation"
+                );
+                ensure!(
+                    epoch.minimum_witness_for_claim_creation <= epoch.witness.len() as u32,
+                    "Epoch requires more witnesses than it has"
+                );
+                let mut addresses: Vec<[u8; 20]> =
+                    epoch.witness.iter().map(|w| w.address).collect();
+                addresses.sort();
+                addresses.dedup();
+                ensure!(
+                    addresses.len() == epoch.witness.len(),
+                    "Epoch has duplicate witness addresses"
+                );
+            }
+
+            Ok(())
+        }
+
+        /// Emits `ProofVerified` for a proof submitted by `who` and verified in `epoch_id`.
+        fn deposit_proof_verified(who: &T::AccountId, proof: &Proof, epoch_id: u64) {
+            let claim = &proof.signedClaim.claim;
+            Self::deposit_event(Event::ProofVerified {
+                who: who.clone(),
+                identifier: claim.identifier.clone(),
+                owner: claim.owner.clone(),
+                provider_hash: proof.claimInfo.provider_hash(),
+                epoch_id,
+                timestamp: claim.timestampS,
+            });
+        }
+
+        /// Verifies a proof submitted by `who` against the current epoch, reporting the weight of
+        /// the checks actually performed. The claim is only stored with a deposit if
+        /// `reserve_deposit` is set.
+        fn do_verify_proof(
+            who: &T::AccountId,
+            proof: &Proof,
+            reserve_deposit: bool,
+        ) -> DispatchResultWithPostInfo {
+            let mut progress = ProofCheckProgress::default();
+            let result = Self::ensure_within_rate_limit(who)
+                .and_then(|()| Self::check_proof(proof, &mut progress));
+            let actual_weight = Self::proof_check_weight(proof, progress);
+            let current_epoch = result.map_err(|error| error.with_weight(actual_weight))?;
+            Self::record_verified_claim(who, proof, &current_epoch, reserve_deposit)
+                .map_err(|error| error.with_weight(actual_weight))?;
+            Self::note_proof_attempt(who, true);
+
+            // Emit event for successful proof verification
+            Self::deposit_proof_verified(who, proof, current_epoch.id);
+
+            Ok(Some(actual_weight.saturating_add(T::OnProofVerified::on_proof_verified_weight()))
+                .into())
+        }
+
+        /// Records the claim of a proof submitted by `who` and verified in `epoch`, and calls
+        /// `OnProofVerified` with it. Fails if the claim was revoked.
+        ///
+        /// The claim is stored with the deposit reserved from `who` if `reserve_deposit` is set,
+        /// unless storing verified claims is disabled or it is already stored. Its attestation is
+        /// stored on its first verification either way.
+        fn record_verified_claim(
+            who: &T::AccountId,
+            proof: &Proof,
+            epoch: &Epoch,
+            reserve_deposit: bool,
+        ) -> DispatchResult {
+            let claim = &proof.signedClaim.claim;
+            let (identifier, owner) = Self::decode_claim(claim)?;
+            let context = proof.claimInfo.parse_context().unwrap_or_default();
+            let mut verified_claim = VerifiedClaim {
+                submitter: who.clone(),
+                owner,
+                epoch: epoch.id,
+                block: frame_system::Pallet::<T>::block_number(),
+                provider_hash: proof.claimInfo.provider_hash(),
+                parameters_hash: T::Hashing::hash_of(&context.extracted_parameters),
+                deposit: Zero::zero(),
+            };
+
+            if let Some(deposit) = T::VerifiedClaimDeposit::get().filter(|_| reserve_deposit) {
+                if !<VerifiedClaims<T>>::contains_key(identifier) {
+                    T::Currency::reserve(who, deposit)?;
+                    verified_claim.deposit = deposit;
+                    <VerifiedClaims<T>>::insert(identifier, &verified_claim);
+                }
+            }
+            if !<ClaimAttestations<T>>::contains_key(identifier) {
+                <ClaimAttestations<T>>::insert(
+                    identifier,
+                    ClaimAttestation { claim: verified_claim.clone(), timestamp: claim.timestampS },
+                );
+            }
+            T::OnProofVerified::on_proof_verified(who, &identifier, &verified_claim);
+            Ok(())
+        }
+
+        /// Decodes the identifier and owner of a claim to record, failing if the claim was
+        /// revoked.
+        fn decode_claim(
+            claim: &CompleteClaimData,
+        ) -> Result<([u8; 32], BoundedVec<u8, ConstU32<64>>), Error<T>> {
+            let mut identifier = [0_u8; 32];
+            hex::decode_to_slice(claim.identifier.trim_start_matches("0x"), &mut identifier)
+                .map_err(|_| Error::<T>::InvalidClaimIdentifier)?;
+            ensure!(!<RevokedClaims<T>>::contains_key(identifier), Error::<T>::RevokedClaim);
+            let owner = hex::decode(claim.owner.trim_start_matches("0x"))
+                .ok()
+                .and_then(|owner| BoundedVec::try_from(owner).ok())
+                .ok_or(Error::<T>::InvalidClaimOwner)?;
+            Ok((identifier, owner))
+        }
+
+        /// Returns whether a proof check failed on an error the proof already had when submitted,
+        /// rather than on a change of state since, e.g. the revocation of a witness.
+        fn is_submission_error(error: &Error<T>) -> bool {
+            matches!(
+                error,
+                Error::<T>::HashMismatch |
+                    Error::<T>::LengthMismatch |
+                    Error::<T>::InvalidSignature |
+                    Error::<T>::SignatureMismatch
+            )
+        }
+
+        /// Drops a pending optimistic proof that can't be recorded, releasing the bond of its
+        /// submitter.
+        fn drop_pending_proof(proof_id: u64, pending: &PendingProofOf<T>, error: DispatchError) {
+            <PendingProofs<T>>::remove(proof_id);
+            T::Currency::unreserve(&pending.submitter, pending.bond);
+            Self::deposit_event(Event::OptimisticProofDropped { proof_id, error });
+        }
+
+        /// Releases the bond of a pending optimistic proof and records its claim as verified in
+        /// the epoch it was submitted in, unless a witness expected to sign it was revoked since.
+        /// Does nothing if the proof is no longer pending.
+        ///
+        /// While verification is paused for the proof, it is left pending with its bond and
+        /// rescheduled with `defer_pending_proof`.
+        fn finalize_pending_proof(proof_id: u64) {
+            let Some(pending) = <PendingProofs<T>>::get(proof_id) else { return };
+            let proof = match pending.decode_proof() {
+                Ok(proof) => proof,
+                Err(error) => return Self::drop_pending_proof(proof_id, &pending, error),
+            };
+            let epoch = <Epochs<T>>::get(pending.epoch);
+            if epoch
+                .as_ref()
+                .map_or(false, |epoch| Self::ensure_not_paused(&proof, epoch).is_err())
+            {
+                return Self::defer_pending_proof(proof_id, pending)
+            }
+            <PendingProofs<T>>::remove(proof_id);
+            T::Currency::unreserve(&pending.submitter, pending.bond);
+            let recorded = with_storage_layer(|| {
+                let epoch = epoch.ok_or(Error::<T>::EpochNotFound)?;
+                Self::ensure_witnesses_not_revoked(&proof, &epoch)?;
+                Self::record_verified_claim(&pending.submitter, &proof, &epoch, true)
+            });
+            match recorded {
+                Ok(()) => {
+                    Self::deposit_event(Event::OptimisticProofFinalized { proof_id });
+                    Self::deposit_proof_verified(&pending.submitter, &proof, pending.epoch);
+                },
+                Err(error) =>
+                    Self::deposit_event(Event::OptimisticProofDropped { proof_id, error }),
+            }
+        }
+
+        /// Reschedules a pending optimistic proof one challenge period later, or in one of the
+        /// `MAX_DEFERRAL_BLOCKS` blocks after with room left. If none has room, the proof stays
+        /// pending until a `challenge` finalizes it.
+        fn defer_pending_proof(proof_id: u64, mut pending: PendingProofOf<T>) {
+            let now = frame_system::Pallet::<T>::block_number();
+            let mut expires_at = now.saturating_add(T::ChallengePeriod::get().max(One::one()));
+            for _ in 0..MAX_DEFERRAL_BLOCKS {
+                let scheduled = <PendingExpiries<T>>::try_mutate(expires_at, |expiring| {
+                    expiring.try_push(proof_id)
+                });
+                if scheduled.is_ok() {
+                    pending.expires_at = expires_at;
+                    <PendingProofs<T>>::insert(proof_id, pending);
+                    break
+                }
+                expires_at = expires_at.saturating_add(One::one());
+            }
+            Self::deposit_event(Event::OptimisticProofDeferred { proof_id });
+        }
+
+        /// Ensures no witness of `epoch` expected to sign the claim of `proof` was revoked.
+        fn ensure_witnesses_not_revoked(proof: &Proof, epoch: &Epoch) -> Result<(), Error<T>> {
+            let claim = &proof.signedClaim.claim;
+            let revoked =
+                fetch_witness_for_claim(epoch.clone(), claim.identifier.clone(), claim.timestampS)
+                    .iter()
+                    .any(|witness| Self::is_witness_revoked(&witness.address));
+            ensure!(!revoked, Error::<T>::RevokedWitness);
+            Ok(())
+        }
+
+        /// Returns the current epoch.
+        fn current_epoch() -> Result<Epoch, Error<T>> {
+            let config = <PReclaimConfig<T>>::get().ok_or(Error::<T>::NotInitialized)?;
+            <Epochs<T>>::get(config.current_epoch).ok_or(Error::<T>::EpochNotFound)
+        }
+
+        /// Performs the checks of a proof that need no signer recovery, returning the current
+        /// epoch and the addresses of the witnesses expected to sign the claim.
+        pub fn check_proof_structure(
+            proof: &Proof,
+            progress: &mut ProofCheckProgress,
+        ) -> Result<(Epoch, Vec<String>), Error<T>> {
+            let current_epoch = Self::current_epoch()?;
+            Self::ensure_not_paused(proof, &current_epoch)?;
+            let expected_witness_addresses =
+                Self::expected_witnesses(proof, &current_epoch, progress)?;
+            Ok((current_epoch, expected_witness_addresses))
+        }
+
+        /// Fully checks a proof against the current epoch, returning the epoch it is valid in.
+        /// Stops at the first signature that fails, so `progress` reflects the work done.
+        pub fn check_proof(
+            proof: &Proof,
+            progress: &mut ProofCheckProgress,
+        ) -> Result<Epoch, Error<T>> {
+            let current_epoch = Self::current_epoch()?;
+            Self::check_proof_in_epoch(proof, &current_epoch, progress)?;
+            Ok(current_epoch)
+        }
+
+        /// Fully checks a proof against `current_epoch`, already read by the caller.
+        pub fn check_proof_in_epoch(
+            proof: &Proof,
+            current_epoch: &Epoch,
+            progress: &mut ProofCheckProgress,
+        ) -> Result<(), Error<T>> {
+            Self::ensure_not_paused(proof, current_epoch)?;
+            let expected_witness_addresses =
+                Self::expected_witnesses(proof, current_epoch, progress)?;
+            let message = T::WitnessSignatureScheme::message(&proof.signedClaim.claim);
+
+            // Verify that each signed witness matches an expected witness address
+            for complete_signature in &proof.signedClaim.signatures {
+                progress.signatures = progress.signatures.saturating_add(1);
+                let signed = T::WitnessSignatureScheme::recover_signer(&message, complete_signature)
+                    .ok_or(Error::<T>::InvalidSignature)?;
+                ensure!(
+                    expected_witness_addresses.contains(&hex::encode(signed)),
+                    Error::<T>::SignatureMismatch
+                );
+                ensure!(!Self::is_witness_revoked(&signed), Error::<T>::RevokedWitness);
+            }
 
             Ok(())
         }
+
+        /// Returns whether proof verification is paused in `scope`.
+        pub fn is_paused(scope: PauseScope) -> bool {
+            <Paused<T>>::contains_key(scope)
+        }
+
+        /// Returns whether the signatures of the witness at `address` are refused.
+        pub fn is_witness_revoked(address: &[u8; 20]) -> bool {
+            <RevokedWitnesses<T>>::contains_key(address)
+        }
+
+        /// Returns whether `signatures` is a single Ethereum-style signature of the revocation
+        /// of `claim` by its owner.
+        fn is_signed_by_owner(
+            identifier: &[u8; 32],
+            claim: &VerifiedClaimOf<T>,
+            signatures: &[String],
+        ) -> bool {
+            let [signature] = signatures else { return false };
+            let message = keccak256_eth(&revocation_text(identifier));
+            recover_signer(&message, signature).map_or(false, |signer| signer == *claim.owner)
+        }
+
+        /// Returns whether `signatures` are signatures of the revocation of a claim by every
+        /// unrevoked witness of its `attestation`, of which there must be at least one.
+        fn is_signed_by_witnesses(
+            identifier: &[u8; 32],
+            attestation: &ClaimAttestationOf<T>,
+            signatures: &[String],
+        ) -> bool {
+            let message = T::WitnessSignatureScheme::revocation_message(identifier);
+            let signers: Vec<[u8; 20]> = signatures
+                .iter()
+                .filter_map(|signature| {
+                    T::WitnessSignatureScheme::recover_signer(&message, signature)
+                })
+                .collect();
+            let mut required: Vec<[u8; 20]> = Self::witnesses_of(identifier, attestation)
+                .into_iter()
+                .filter(|witness| !Self::is_witness_revoked(witness))
+                .collect();
+            required.sort();
+            required.dedup();
+            !required.is_empty() && required.iter().all(|witness| signers.contains(witness))
+        }
+
+        /// Returns the addresses of the witnesses that attested the claim with `identifier`,
+        /// selected again from the epoch of its `attestation`.
+        fn witnesses_of(
+            identifier: &[u8; 32],
+            attestation: &ClaimAttestationOf<T>,
+        ) -> Vec<[u8; 20]> {
+            let Some(epoch) = <Epochs<T>>::get(attestation.claim.epoch) else { return Vec::new() };
+            let identifier = format!("0x{}", hex::encode(identifier));
+            fetch_witness_for_claim(epoch, identifier, attestation.timestamp)
+                .into_iter()
+                .map(|witness| witness.address)
+                .collect()
+        }
+
+        /// Returns the addresses of the witnesses that attested the verified claim with
+        /// `identifier`, or an empty list if it was never verified.
+        pub fn claim_witnesses(identifier: &[u8; 32]) -> Vec<[u8; 20]> {
+            <ClaimAttestations<T>>::get(identifier)
+                .map(|attestation| Self::witnesses_of(identifier, &attestation))
+                .unwrap_or_default()
+        }
+
+        /// Returns whether the claim with `identifier` has been revoked.
+        pub fn is_claim_revoked(identifier: &[u8; 32]) -> bool {
+            <RevokedClaims<T>>::contains_key(identifier)
+        }
+
+        /// Returns the addresses of the witnesses of the current epoch expected to sign the claim
+        /// of `proof`, or an empty list if Reclaim is not initialized.
+        pub fn attesting_witnesses(proof: &Proof) -> Vec<[u8; 20]> {
+            let Ok(current_epoch) = Self::current_epoch() else { return Vec::new() };
+            let claim = &proof.signedClaim.claim;
+            fetch_witness_for_claim(current_epoch, claim.identifier.clone(), claim.timestampS)
+                .into_iter()
+                .map(|witness| witness.address)
+                .collect()
+        }
+
+        /// Ensures verification is paused neither for all proofs, nor for `current_epoch`, nor
+        /// for the provider of `proof`.
+        fn ensure_not_paused(proof: &Proof, current_epoch: &Epoch) -> Result<(), Error<T>> {
+            let paused = Self::is_paused(PauseScope::All) ||
+                Self::is_paused(PauseScope::Epoch(current_epoch.id)) ||
+                proof
+                    .claimInfo
+                    .provider_hash()
+                    .map_or(false, |hash| Self::is_paused(PauseScope::Provider(hash)));
+            ensure!(!paused, Error::<T>::Paused);
+            Ok(())
+        }
+
+        /// Checks the claim hash and signature count of a proof, returning the addresses of the
+        /// witnesses of `current_epoch` expected to sign the claim.
+        fn expected_witnesses(
+            proof: &Proof,
+            current_epoch: &Epoch,
+            progress: &mut ProofCheckProgress,
+        ) -> Result<Vec<String>, Error<T>> {
+            progress.witnesses = current_epoch.witness.len() as u32;
+            ensure!(!current_epoch.witness.is_empty(), Error::<T>::EmptyWitnessSet);
+            let signed_claim = &proof.signedClaim;
+
+            // Check if the claim's identifier matches the computed hash
+            ensure!(
+                signed_claim.claim.identifier == proof.claimInfo.hash(),
+                Error::<T>::HashMismatch
+            );
+
+            // Fetch expected witnesses for the claim
+            let expected_witness = fetch_witness_for_claim(
+                current_epoch.clone(),
+                signed_claim.claim.identifier.clone(),
+                signed_claim.claim.timestampS,
+            );
+            let expected_witness_addresses = Witness::get_addresses(expected_witness);
+
+            // Check if the number of expected witnesses matches the number of signatures
+            ensure!(
+                expected_witness_addresses.len() == signed_claim.signatures.len(),
+                Error::<T>::LengthMismatch
+            );
+
+            Ok(expected_witness_addresses)
+        }
+
+        /// Returns the submissions of `who` in the rate limit window of the current block, or
+        /// `None` if rate limiting is disabled.
+        fn current_attempts(who: &T::AccountId) -> Option<ProofAttempts<BlockNumberFor<T>>> {
+            let window = T::ProofAttemptWindow::get();
+            if window.is_zero() {
+                return None
+            }
+            let now = frame_system::Pallet::<T>::block_number();
+            let mut attempts = <AccountProofAttempts<T>>::get(who).unwrap_or_default();
+            if now >= attempts.window_start.saturating_add(window) {
+                attempts.window_start = now;
+                attempts.attempts = 0;
+            }
+            Some(attempts)
+        }
+
+        /// Ensures `who` has proof submissions left in its current rate limit window.
+        pub fn ensure_within_rate_limit(who: &T::AccountId) -> Result<(), Error<T>> {
+            let Some(attempts) = Self::current_attempts(who) else { return Ok(()) };
+            ensure!(attempts.attempts < T::MaxProofAttempts::get(), Error::<T>::RateLimited);
+            Ok(())
+        }
+
+        /// Counts a proof submission of `who` against its rate limit. A successful submission
+        /// counts once and clears the failures, each consecutive failure counts twice as much as
+        /// the previous one.
+        ///
+        /// Verified proofs are counted as they are recorded. Calls failing on a rejected proof
+        /// are rolled back, so `CheckReclaimProof` counts their failure after dispatch.
+        pub fn note_proof_attempt(who: &T::AccountId, succeeded: bool) {
+            let Some(mut attempts) = Self::current_attempts(who) else { return };
+            let cost = if succeeded {
+                attempts.failures = 0;
+                1
+            } else {
+                let cost = 1_u32.checked_shl(attempts.failures).unwrap_or(u32::MAX);
+                attempts.failures = attempts.failures.saturating_add(1);
+                cost
+            };
+            attempts.attempts = attempts.attempts.saturating_add(cost);
+            <AccountProofAttempts<T>>::insert(who, attempts);
+        }
+
+        /// Returns the weight of a proof check that reached `progress`.
+        pub fn proof_check_weight(proof: &Proof, progress: ProofCheckProgress) -> Weight {
+            <T as pallet::Config>::WeightInfo::verify_proof(
+                progress.signatures,
+                progress.witnesses,
+                proof.claimInfo.size(),
+            )
+        }
+
+        /// Returns the worst-case weight of `challenge`, verifying a proof of the maximum size
+        /// with `signatures` signatures and finalizing it.
+        pub fn challenge_weight(signatures: u32) -> Weight {
+            <T as pallet::Config>::WeightInfo::challenge()
+                .saturating_add(<T as pallet::Config>::WeightInfo::verify_proof(
+                    signatures,
+                    MAX_WITNESSES,
+                    T::MaxOptimisticProofSize::get(),
+                ))
+                .saturating_add(T::OnProofVerified::on_proof_verified_weight())
+        }
+
+        /// Returns the worst-case weight of verifying `proofs` with `verify_proofs`.
+        pub fn verify_proofs_weight(proofs: &[Proof]) -> Weight {
+            proofs.iter().fold(Weight::zero(), |total, proof| {
+                total.saturating_add(
+                    <Self as ReclaimVerifier<T::AccountId, Proof>>::verify_proof_weight(proof),
+                )
+            })
+        }
+
+        /// Fetches the epoch source and, when its witness set is signed by an epoch source signer
+        /// and differs from the current epoch, submits a signed `sync_epoch` transaction from a
+        /// local key of an epoch syncer.
+        pub fn sync_epoch_from_source() -> Result<(), &'static str> {
+            let config = <PReclaimConfig<T>>::get().ok_or("Reclaim is not initialized")?;
+            let url = offchain::epoch_source_url(T::EpochSourceUrl::get());
+            let url = core::str::from_utf8(&url).map_err(|_| "Epoch source URL is not UTF-8")?;
+            let source = offchain::fetch_epoch(url).map_err(|_| "Failed to fetch epoch source")?;
+            Self::ensure_signed_by_epoch_source(
+                config.current_epoch + 1_u64,
+                &source.witness,
+                source.minimum_witness,
+                &source.signature,
+            )
+            .map_err(|_| "Epoch source is not signed by an epoch source signer")?;
+            if Self::is_current_epoch(config.current_epoch, &source.witness, source.minimum_witness)
+            {
+                return Ok(())
+            }
+
+            // Only sign with local keys that belong to the epoch signer set
+            let mut allowed = <EpochSyncers<T>>::get().into_inner();
+            allowed.push(config.owner);
+            let signers = AppPublicOf::<T>::all()
+                .into_iter()
+                .map(|key| -> <T as SigningTypes>::Public { GenericPublicOf::<T>::from(key).into() })
+                .filter(|public| allowed.contains(&public.clone().into_account()))
+                .collect::<Vec<_>>();
+            if signers.is_empty() {
+                return Err("No local key belongs to an epoch syncer")
+            }
+
+            let result = Signer::<T, T::AuthorityId>::any_account()
+                .with_filter(signers)
+                .send_signed_transaction(|_account| Call::sync_epoch {
+                    witness: source.witness.clone(),
+                    minimum_witness: source.minimum_witness,
+                    signature: source.signature.clone(),
+                });
+            match result {
+                Some((_, Ok(()))) => Ok(()),
+                Some((_, Err(()))) => Err("Failed to submit epoch sync transaction"),
+                None => Err("No local account available to sign epoch sync"),
+            }
+        }
     }
 }
 
-impl<T> ReclaimVerifier<Proof> for Pallet<T>
+impl<T> ReclaimVerifier<T::AccountId, Proof> for Pallet<T>
 where
     T: Config,
 {
-    /// Verifies the proof using the current Reclaim configuration.
-    fn verify_proof(proof: &Proof) -> DispatchResult {
-        let config = <PReclaimConfig<T>>::get().unwrap();
-        let epoch_count = config.current_epoch;
-        let current_epoch = <Epochs<T>>::get(epoch_count);
-
-        let signed_claim = proof.signedClaim.clone();
-        let hashed = proof.claimInfo.hash();
-
-        // Check if the claim's identifier matches the computed hash
-        ensure!(signed_claim.claim.identifier == hashed, Error::<T>::HashMismatch);
-
-        // Fetch expected witnesses for the claim
-        let expected_witness = fetch_witness_for_claim(
-            current_epoch.clone(),
-            signed_claim.claim.identifier.clone(),
-            signed_claim.claim.timestampS,
-        );
-
-        let expected_witness_addresses = Witness::get_addresses(expected_witness);
-        let signed_witness = signed_claim.recover_signers_of_signed_claim();
-
-        // Check if the number of expected witnesses matches the number of signatures
-        ensure!(
-            expected_witness_addresses.len() == signed_witness.len(),
-            Error::<T>::LengthMismatch
-        );
-
-        // Verify that each signed witness matches an expected witness address
-        for signed in signed_witness {
-            ensure!(
-                expected_witness_addresses.contains(&hex::encode(signed)),
-                Error::<T>::SignatureMismatch
-            );
-        }
+    /// Verifies the proof using the current Reclaim configuration, reporting the weight of the
+    /// checks actually performed.
+    fn verify_proof(who: &T::AccountId, proof: &Proof) -> DispatchResultWithPostInfo {
+        Self::do_verify_proof(who, proof, true)
+    }
 
-        // Emit event for successful proof verification
-        Self::deposit_event(Event::ProofVerified { epoch_id: current_epoch.id });
+    /// Verifies the proof like `verify_proof`, without reserving the claim deposit from `who`.
+    fn verify_proof_without_deposit(
+        who: &T::AccountId,
+        proof: &Proof,
+    ) -> DispatchResultWithPostInfo {
+        Self::do_verify_proof(who, proof, false)
+    }
+
+    /// Returns the weight of verifying the proof against the largest witness set and calling
+    /// `OnProofVerified`. Its signatures bound the recoveries, as a proof with more signatures
+    /// than expected witnesses fails with `LengthMismatch` before any recovery.
+    fn verify_proof_weight(proof: &Proof) -> Weight {
+        <T as pallet::Config>::WeightInfo::verify_proof(
+            proof.signedClaim.signatures.len() as u32,
+            MAX_WITNESSES,
+            proof.claimInfo.size(),
+        )
+        .saturating_add(T::OnProofVerified::on_proof_verified_weight())
+    }
 
+    /// Runs the checks of `verify_proof` against the current Reclaim configuration, including
+    /// the rate limit of `who` and the revocation of the claim, without side effects.
+    fn validate_proof(who: &T::AccountId, proof: &Proof) -> DispatchResult {
+        Self::ensure_within_rate_limit(who)?;
+        Self::check_proof(proof, &mut Default::default())?;
+        Self::decode_claim(&proof.signedClaim.claim)?;
         Ok(())
     }
+
+    /// Returns the witnesses of the current epoch expected to sign the claim of the proof.
+    fn attesting_witnesses(proof: &Proof) -> Vec<[u8; 20]> {
+        Pallet::<T>::attesting_witnesses(proof)
+    }
+
+    /// Checks the revocation list of the pallet.
+    fn is_witness_revoked(address: &[u8; 20]) -> bool {
+        Pallet::<T>::is_witness_revoked(address)
+    }
+
+    /// Checks the revoked claims of the pallet.
+    fn is_claim_revoked(identifier: &[u8; 32]) -> bool {
+        Pallet::<T>::is_claim_revoked(identifier)
+    }
+}
+
+impl<T> ProofCall<Proof> for Call<T>
+where
+    T: Config,
+{
+    /// Returns the proof submitted through `verify_proof`.
+    fn reclaim_proof(&self) -> Option<Proof> {
+        match self {
+            Call::verify_proof { claim_info, signed_claim } => Some(Proof {
+                claimInfo: claim_info.clone(),
+                signedClaim: signed_claim.clone(),
+            }),
+            _ => None,
+        }
+    }
 }
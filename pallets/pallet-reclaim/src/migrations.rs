@@ -0,0 +1,144 @@
+//! Storage migrations of pallet-reclaim.
+//!
+//! Each `vN` module holds the migration to storage version `N`. Migrations check the on-chain
+//! storage version and do nothing once it has been reached, so they can stay in a runtime's
+//! migration set until every chain running it is upgraded.
+use crate::{Config, Epoch, Epochs, PReclaimConfig, Pallet, Witness};
+use codec::{Decode, Encode};
+use core::marker::PhantomData;
+use frame_support::{
+	pallet_prelude::ConstU32,
+	traits::{Get, GetStorageVersion, OnRuntimeUpgrade, StorageVersion},
+	weights::Weight,
+};
+use sp_runtime::{BoundedVec, SaturatedConversion};
+#[cfg(feature = "try-runtime")]
+use frame_support::ensure;
+#[cfg(feature = "try-runtime")]
+use scale_info::prelude::vec::Vec;
+#[cfg(feature = "try-runtime")]
+use sp_runtime::TryRuntimeError;
+
+/// Migration to storage version 1.
+pub mod v1 {
+	use super::*;
+
+	/// Records storage version 1 on chains deployed before the pallet was versioned.
+	///
+	/// Version 1 keeps the layout of the unversioned storage, so no value is translated.
+	pub struct MigrateToV1<T>(PhantomData<T>);
+
+	impl<T: Config> OnRuntimeUpgrade for MigrateToV1<T> {
+		fn on_runtime_upgrade() -> Weight {
+			let on_chain_version = Pallet::<T>::on_chain_storage_version();
+			if on_chain_version != 0 {
+				log::info!(
+					target: "runtime::reclaim",
+					"Skipping migration to v1, storage is at {:?}",
+					on_chain_version
+				);
+				return T::DbWeight::get().reads(1)
+			}
+
+			StorageVersion::new(1).put::<Pallet<T>>();
+			log::info!(target: "runtime::reclaim", "Migrated storage to v1");
+			T::DbWeight::get().reads_writes(1, 1)
+		}
+
+		#[cfg(feature = "try-runtime")]
+		fn pre_upgrade() -> Result<Vec<u8>, TryRuntimeError> {
+			let epochs = Epochs::<T>::iter_keys().count() as u32;
+			Ok(epochs.encode())
+		}
+
+		#[cfg(feature = "try-runtime")]
+		fn post_upgrade(state: Vec<u8>) -> Result<(), TryRuntimeError> {
+			let epochs = u32::decode(&mut &state[..])
+				.map_err(|_| "Failed to decode the epoch count of pre_upgrade")?;
+			ensure!(
+				Pallet::<T>::on_chain_storage_version() >= 1,
+				"Storage version was not updated to v1"
+			);
+			// Epochs that no longer decode are skipped by `iter_values`
+			ensure!(
+				Epochs::<T>::iter_values().count() as u32 == epochs,
+				"Epochs were lost or no longer decode"
+			);
+			ensure!(
+				!PReclaimConfig::<T>::exists() || PReclaimConfig::<T>::get().is_some(),
+				"Reclaim config no longer decodes"
+			);
+			Ok(())
+		}
+	}
+}
+
+/// Migration to storage version 2.
+pub mod v2 {
+	use super::*;
+
+	/// Layout of `Epoch` up to storage version 1, with a `u128` minimum witness count.
+	#[derive(Encode, Decode, Clone, PartialEq, Eq, Debug)]
+	pub struct OldEpoch {
+		pub id: u64,                                      // Epoch identifier
+		pub timestamp_start: u64,                         // Start timestamp
+		pub timestamp_end: u64,                           // End timestamp
+		pub minimum_witness_for_claim_creation: u128,     // Minimum witnesses needed for claim creation
+		pub witness: BoundedVec<Witness, ConstU32<100>>,  // List of witnesses for the epoch
+	}
+
+	/// Stores the minimum witness count of every epoch as a `u32`.
+	pub struct MigrateToV2<T>(PhantomData<T>);
+
+	impl<T: Config> OnRuntimeUpgrade for MigrateToV2<T> {
+		fn on_runtime_upgrade() -> Weight {
+			let on_chain_version = Pallet::<T>::on_chain_storage_version();
+			if on_chain_version != 1 {
+				log::info!(
+					target: "runtime::reclaim",
+					"Skipping migration to v2, storage is at {:?}",
+					on_chain_version
+				);
+				return T::DbWeight::get().reads(1)
+			}
+
+			let mut translated = 0_u64;
+			Epochs::<T>::translate::<OldEpoch, _>(|_, old| {
+				translated += 1;
+				Some(Epoch {
+					id: old.id,
+					timestamp_start: old.timestamp_start,
+					timestamp_end: old.timestamp_end,
+					minimum_witness_for_claim_creation: old
+						.minimum_witness_for_claim_creation
+						.saturated_into(),
+					witness: old.witness,
+				})
+			});
+			StorageVersion::new(2).put::<Pallet<T>>();
+			log::info!(target: "runtime::reclaim", "Migrated {} epochs to v2", translated);
+			T::DbWeight::get().reads_writes(translated + 1, translated + 1)
+		}
+
+		#[cfg(feature = "try-runtime")]
+		fn pre_upgrade() -> Result<Vec<u8>, TryRuntimeError> {
+			let epochs = Epochs::<T>::iter_keys().count() as u32;
+			Ok(epochs.encode())
+		}
+
+		#[cfg(feature = "try-runtime")]
+		fn post_upgrade(state: Vec<u8>) -> Result<(), TryRuntimeError> {
+			let epochs = u32::decode(&mut &state[..])
+				.map_err(|_| "Failed to decode the epoch count of pre_upgrade")?;
+			ensure!(
+				Pallet::<T>::on_chain_storage_version() >= 2,
+				"Storage version was not updated to v2"
+			);
+			ensure!(
+				Epochs::<T>::iter_values().count() as u32 == epochs,
+				"Epochs were lost or no longer decode"
+			);
+			Ok(())
+		}
+	}
+}
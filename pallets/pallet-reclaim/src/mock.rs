@@ -1,9 +1,20 @@
 use crate as pallet_reclaim;
-use frame_support::traits::{ConstU16, ConstU64};
+use crate::{
+	proof_builder,
+	traits::{OnClaimRevoked, OnProofVerified, ProofCall},
+	Proof, VerifiedClaimOf,
+};
+use frame_support::{
+	dispatch::DispatchResultWithPostInfo,
+	parameter_types,
+	traits::{ConstU16, ConstU32, ConstU64},
+	weights::Weight,
+};
 use sp_core::H256;
 use sp_runtime::{
-	traits::{BlakeTwo256, IdentityLookup},
-	BuildStorage,
+	testing::{TestSignature, TestXt, UintAuthorityId},
+	traits::{BlakeTwo256, Extrinsic as ExtrinsicT, IdentityLookup},
+	BoundedVec, BuildStorage, Perbill,
 };
 
 // Mock block type for testing
@@ -15,6 +26,7 @@ frame_support::construct_runtime!(
 	{
 		System: frame_system,
 		Timestamp: pallet_timestamp,
+		Balances: pallet_balances,
 		Reclaim: pallet_reclaim,
 	}
 );
@@ -36,7 +48,7 @@ impl frame_system::Config for Test {
 	type BlockHashCount = ConstU64<250>;
 	type Version = ();
 	type PalletInfo = PalletInfo;
-	type AccountData = ();
+	type AccountData = pallet_balances::AccountData<u64>;
 	type OnNewAccount = ();
 	type OnKilledAccount = ();
 	type SystemWeightInfo = ();
@@ -52,18 +64,187 @@ impl pallet_timestamp::Config for Test {
 	type WeightInfo = ();
 }
 
+impl pallet_balances::Config for Test {
+	type MaxLocks = ();
+	type MaxReserves = ();
+	type ReserveIdentifier = [u8; 8];
+	type Balance = u64;
+	type RuntimeEvent = RuntimeEvent;
+	type DustRemoval = ();
+	type ExistentialDeposit = ConstU64<1>;
+	type AccountStore = System;
+	type WeightInfo = ();
+	type FreezeIdentifier = ();
+	type MaxFreezes = ();
+	type RuntimeHoldReason = ();
+	type MaxHolds = ();
+}
+
+pub type Extrinsic = TestXt<RuntimeCall, ()>;
+
+impl frame_system::offchain::SigningTypes for Test {
+	type Public = UintAuthorityId;
+	type Signature = TestSignature;
+}
+
+impl<LocalCall> frame_system::offchain::SendTransactionTypes<LocalCall> for Test
+where
+	RuntimeCall: From<LocalCall>,
+{
+	type OverarchingCall = RuntimeCall;
+	type Extrinsic = Extrinsic;
+}
+
+impl<LocalCall> frame_system::offchain::CreateSignedTransaction<LocalCall> for Test
+where
+	RuntimeCall: From<LocalCall>,
+{
+	fn create_transaction<C: frame_system::offchain::AppCrypto<UintAuthorityId, TestSignature>>(
+		call: RuntimeCall,
+		_public: UintAuthorityId,
+		account: u64,
+		_nonce: u64,
+	) -> Option<(RuntimeCall, <Extrinsic as ExtrinsicT>::SignaturePayload)> {
+		Some((call, (account, ())))
+	}
+}
+
+// Signs offchain worker transactions with the keys set through `UintAuthorityId::set_all_keys`.
+pub struct TestAuthId;
+
+impl frame_system::offchain::AppCrypto<UintAuthorityId, TestSignature> for TestAuthId {
+	type RuntimeAppPublic = UintAuthorityId;
+	type GenericPublic = UintAuthorityId;
+	type GenericSignature = TestSignature;
+}
+
+pub const EPOCH_SOURCE_URL: &str = "http://localhost:8001/epoch";
+
+parameter_types! {
+	pub const EpochSourceUrl: &'static str = EPOCH_SOURCE_URL;
+	pub EpochSourceSigners: Vec<[u8; 20]> = vec![proof_builder::epoch_source_signer()];
+	pub static VerifiedClaimDeposit: Option<u64> = None;
+	pub static OptimisticBond: Option<u64> = None;
+	pub static OptimisticBondPerByte: u64 = 0;
+	pub const ChallengerReward: Perbill = Perbill::from_percent(40);
+	pub static VerifiedClaimsSeen: Vec<(u64, [u8; 32])> = Vec::new();
+	pub static RevokedClaimsSeen: Vec<[u8; 32]> = Vec::new();
+}
+
+// Records the claims verified and revoked, to check the hooks are called.
+pub struct RecordClaims;
+
+// Weight declared by each call of `RecordClaims`.
+pub const HOOK_WEIGHT: Weight = Weight::from_parts(1_000_000, 0);
+
+impl OnProofVerified<u64, VerifiedClaimOf<Test>> for RecordClaims {
+	fn on_proof_verified(who: &u64, identifier: &[u8; 32], _claim: &VerifiedClaimOf<Test>) {
+		VerifiedClaimsSeen::mutate(|seen| seen.push((*who, *identifier)));
+	}
+
+	fn on_proof_verified_weight() -> Weight {
+		HOOK_WEIGHT
+	}
+}
+
+impl OnClaimRevoked<VerifiedClaimOf<Test>> for RecordClaims {
+	fn on_claim_revoked(identifier: &[u8; 32], _claim: &VerifiedClaimOf<Test>) {
+		RevokedClaimsSeen::mutate(|seen| seen.push(*identifier));
+	}
+
+	fn on_claim_revoked_weight() -> Weight {
+		HOOK_WEIGHT
+	}
+}
+
 impl pallet_reclaim::Config for Test {
 	type RuntimeEvent = RuntimeEvent;
 	type Signature = sp_core::ecdsa::Signature;
 	type PublicKey = sp_core::ecdsa::Public;
+	type WitnessSignatureScheme = pallet_reclaim::schemes::EthereumWitnesses;
+	type AuthorityId = TestAuthId;
+	type EpochSourceUrl = EpochSourceUrl;
+	type EpochSourceSigners = EpochSourceSigners;
+	type EpochSyncInterval = ConstU64<10>;
+	type MaxBatch = frame_support::traits::ConstU32<10>;
+	type InitOrigin = frame_system::EnsureRoot<u64>;
+	type AdminOrigin = frame_system::EnsureRoot<u64>;
+	type ProofAttemptWindow = ConstU64<10>;
+	type MaxProofAttempts = frame_support::traits::ConstU32<3>;
+	type Currency = Balances;
+	type VerifiedClaimDeposit = VerifiedClaimDeposit;
+	type OnProofVerified = (RecordClaims, RecordClaims);
+	type OnClaimRevoked = (RecordClaims, RecordClaims);
+	type OptimisticBond = OptimisticBond;
+	type OptimisticBondPerByte = OptimisticBondPerByte;
+	type ChallengePeriod = ConstU64<5>;
+	type MaxPendingPerBlock = frame_support::traits::ConstU32<2>;
+	type MaxOptimisticProofSize = frame_support::traits::ConstU32<1024>;
+	type Slash = ();
+	type ChallengerReward = ChallengerReward;
 	type WeightInfo = ();
 }
 
+impl ProofCall<pallet_reclaim::Proof> for RuntimeCall {
+	fn reclaim_proof(&self) -> Option<pallet_reclaim::Proof> {
+		match self {
+			RuntimeCall::Reclaim(call) => ProofCall::reclaim_proof(call),
+			_ => None,
+		}
+	}
+}
+
 // Build genesis storage according to the mock runtime.
 pub fn new_test_ext() -> sp_io::TestExternalities {
 	frame_system::GenesisConfig::<Test>::default().build_storage().unwrap().into()
 }
 
+/// Proof of `proof_builder::http_claim(claim_data)`, signed by the witnesses of epoch 1.
+pub fn epoch_proof(claim_data: &str) -> Proof {
+	let epoch = Reclaim::epochs(1).expect("epoch 1 is added by the test; qed");
+	proof_builder::build_proof(&epoch, proof_builder::http_claim(claim_data), 1712174155)
+}
+
+/// Identifier of the claim proven by `proof`.
+pub fn claim_identifier(proof: &Proof) -> [u8; 32] {
+	let mut identifier = [0_u8; 32];
+	hex::decode_to_slice(&proof.signedClaim.claim.identifier[2..], &mut identifier).unwrap();
+	identifier
+}
+
+/// Indices of the generated witnesses, among the first `count`, attesting the claim of `proof`.
+pub fn attesting_indices(proof: &Proof, count: u32) -> Vec<u32> {
+	let attesting = Reclaim::attesting_witnesses(proof);
+	(0..count)
+		.filter(|index| attesting.contains(&proof_builder::witness(*index).address))
+		.collect()
+}
+
+/// Signatures of the revocation of the claim with `identifier` by the generated witnesses at
+/// `indices`.
+pub fn revocation_signed_by(
+	identifier: &[u8; 32],
+	indices: &[u32],
+) -> BoundedVec<String, ConstU32<100>> {
+	BoundedVec::truncate_from(
+		indices
+			.iter()
+			.map(|index| {
+				proof_builder::sign_revocation(identifier, &proof_builder::witness_key(*index))
+			})
+			.collect(),
+	)
+}
+
+/// Submits `proof` to `verify_proof` as `who`.
+pub fn verify_as(who: u64, proof: &Proof) -> DispatchResultWithPostInfo {
+	Reclaim::verify_proof(
+		RuntimeOrigin::signed(who),
+		proof.claimInfo.clone(),
+		proof.signedClaim.clone(),
+	)
+}
+
 // Constants for testing
 const _ZERO_ADDRESS: &str = "00000000000000000000000000000000000000000000000000000000000000";
 const _ZERO_HOST: &str = "00000000000000000000000000000000000000000000000000000000000000";
@@ -0,0 +1,105 @@
+//! Offchain worker support for syncing witness epochs from an external source.
+use crate::Witness;
+use frame_support::{pallet_prelude::ConstU32, sp_runtime::BoundedVec};
+use scale_info::prelude::{string::String, vec::Vec};
+use serde::Deserialize;
+use sp_core::crypto::KeyTypeId;
+use sp_runtime::offchain::{http, Duration, StorageKind};
+
+/// Key type used by the offchain worker to sign epoch sync transactions.
+pub const KEY_TYPE: KeyTypeId = KeyTypeId(*b"recl");
+
+/// Local storage key that lets a node operator override `Config::EpochSourceUrl`.
+pub const EPOCH_SOURCE_URL_KEY: &[u8] = b"reclaim::epoch-source-url";
+
+/// Time allowed for the epoch source to respond, in milliseconds.
+const FETCH_TIMEOUT_MS: u64 = 3_000;
+
+/// Application crypto used to sign transactions sent by the offchain worker.
+pub mod crypto {
+	use super::KEY_TYPE;
+	use sp_runtime::{
+		app_crypto::{app_crypto, sr25519},
+		MultiSignature, MultiSigner,
+	};
+	app_crypto!(sr25519, KEY_TYPE);
+
+	pub struct ReclaimAuthId;
+
+	impl frame_system::offchain::AppCrypto<MultiSigner, MultiSignature> for ReclaimAuthId {
+		type RuntimeAppPublic = Public;
+		type GenericSignature = sp_core::sr25519::Signature;
+		type GenericPublic = sp_core::sr25519::Public;
+	}
+}
+
+/// Witness entry as served by the epoch source.
+#[derive(Deserialize)]
+struct WitnessPayload {
+	address: String, // Hex-encoded 20-byte address
+	host: String,    // Hex-encoded 32-byte host identifier
+}
+
+/// Epoch as served by the epoch source.
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct EpochPayload {
+	minimum_witnesses_for_claim_creation: u32,
+	witnesses: Vec<WitnessPayload>,
+	signature: String, // Hex-encoded signature of the epoch by an epoch source signer
+}
+
+/// Witness set fetched from the epoch source.
+#[derive(Clone, PartialEq, Debug)]
+pub struct SourceEpoch {
+	pub witness: BoundedVec<Witness, ConstU32<100>>, // Witnesses of the epoch
+	pub minimum_witness: u32,                        // Minimum witnesses needed for claim creation
+	pub signature: String,                           // Signature of the epoch by the epoch source
+}
+
+/// Decodes a hex string, with or without a `0x` prefix, into a fixed-size array.
+fn decode_hex_array<const N: usize>(content: &str) -> Option<[u8; N]> {
+	let mut bytes = [0_u8; N];
+	hex::decode_to_slice(content.trim_start_matches("0x"), &mut bytes).ok()?;
+	Some(bytes)
+}
+
+/// Parses the JSON body served by the epoch source.
+pub fn parse_epoch(body: &[u8]) -> Option<SourceEpoch> {
+	let payload: EpochPayload = serde_json::from_slice(body).ok()?;
+	let mut witness = Vec::new();
+	for wit in payload.witnesses {
+		witness.push(Witness {
+			address: decode_hex_array(&wit.address)?,
+			host: decode_hex_array(&wit.host)?,
+		});
+	}
+	Some(SourceEpoch {
+		witness: BoundedVec::try_from(witness).ok()?,
+		minimum_witness: payload.minimum_witnesses_for_claim_creation,
+		signature: payload.signature,
+	})
+}
+
+/// Returns the epoch source URL, preferring the node-local override over the runtime default.
+pub fn epoch_source_url(default: &str) -> Vec<u8> {
+	sp_io::offchain::local_storage_get(StorageKind::PERSISTENT, EPOCH_SOURCE_URL_KEY)
+		.unwrap_or_else(|| default.as_bytes().to_vec())
+}
+
+/// Fetches and parses the current epoch from the given URL.
+pub fn fetch_epoch(url: &str) -> Result<SourceEpoch, http::Error> {
+	let deadline = sp_io::offchain::timestamp().add(Duration::from_millis(FETCH_TIMEOUT_MS));
+	let pending = http::Request::get(url)
+		.deadline(deadline)
+		.send()
+		.map_err(|_| http::Error::IoError)?;
+	let response = pending.try_wait(deadline).map_err(|_| http::Error::DeadlineReached)??;
+	if response.code != 200 {
+		log::warn!(target: "runtime::reclaim", "Unexpected status code: {}", response.code);
+		return Err(http::Error::Unknown)
+	}
+
+	let body = response.body().collect::<Vec<u8>>();
+	parse_epoch(&body).ok_or(http::Error::Unknown)
+}
@@ -0,0 +1,126 @@
+//! Generates witnesses and proofs signed by them, for tests and benchmarks.
+use crate::{
+	epoch_text, fetch_witness_for_claim, keccak256_eth, revocation_text, ClaimInfo,
+	CompleteClaimData, Epoch, Proof, SignedClaim, Witness,
+};
+use frame_support::{pallet_prelude::ConstU32, sp_runtime::BoundedVec};
+use k256::ecdsa::SigningKey;
+use scale_info::prelude::{format, string::String, vec::Vec};
+use sha3::{Digest, Keccak256};
+
+/// Owner recorded in generated claims.
+pub const CLAIM_OWNER: &str = "0x13239fc6bf3847dfedaf067968141ec0363ca42f";
+
+/// Provider hash declared in contexts built by `claim_context`.
+pub const PROVIDER_HASH: &str =
+	"0xffd5f761e0fb207368d9ebf9689f077352ab5d20ae0a2c23584c2cd90fc1b1bf";
+
+/// Index of the generated key the epoch source signs epochs with. Runtimes list the address
+/// of `epoch_source_signer` in `Config::EpochSourceSigners` to benchmark `sync_epoch`.
+pub const EPOCH_SOURCE_KEY: u32 = 100;
+
+/// Deterministic signing key of the witness at `index`.
+pub fn witness_key(index: u32) -> SigningKey {
+	let mut seed = Keccak256::new_with_prefix(b"reclaim-witness");
+	seed.update(index.to_le_bytes());
+	SigningKey::from_slice(seed.finalize().as_slice()).expect("keccak output is a valid key; qed")
+}
+
+/// Ethereum address of the holder of `key`.
+pub fn address(key: &SigningKey) -> [u8; 20] {
+	let point = key.verifying_key().to_encoded_point(false);
+	let hash = Keccak256::digest(&point.as_bytes()[1..]);
+	let mut address = [0_u8; 20];
+	address.copy_from_slice(&hash[12..]);
+	address
+}
+
+/// Address of the generated key the epoch source signs epochs with.
+pub fn epoch_source_signer() -> [u8; 20] {
+	address(&witness_key(EPOCH_SOURCE_KEY))
+}
+
+/// Witness whose address is derived from `witness_key(index)`.
+pub fn witness(index: u32) -> Witness {
+	Witness { address: address(&witness_key(index)), host: [1_u8; 32] }
+}
+
+/// Witness set made of the first `count` generated witnesses.
+pub fn witnesses(count: u32) -> BoundedVec<Witness, ConstU32<100>> {
+	BoundedVec::truncate_from((0..count).map(witness).collect())
+}
+
+/// Context JSON of a claim made for `context_address` with the extracted `CLAIM_DATA`.
+pub fn claim_context(context_address: &str, claim_data: &str) -> String {
+	format!(
+		"{{\"contextAddress\":\"{}\",\"contextMessage\":\"\",\"extractedParameters\":{{\"CLAIM_DATA\":\"{}\"}},\"providerHash\":\"{}\"}}",
+		context_address, claim_data, PROVIDER_HASH
+	)
+}
+
+/// Claim of the `http` provider, made for no context address with the extracted `CLAIM_DATA`.
+pub fn http_claim(claim_data: &str) -> ClaimInfo {
+	ClaimInfo {
+		provider: "http".into(),
+		parameters: "{}".into(),
+		context: claim_context("0x", claim_data),
+	}
+}
+
+/// Signs a claim the way witnesses do, returning the hex-encoded `r || s || v` signature.
+pub fn sign_claim(claim: &CompleteClaimData, key: &SigningKey) -> String {
+	sign_eth(claim.serialise().as_str(), key)
+}
+
+/// Signs the revocation of the claim with `identifier` Ethereum-style, as its owner or an
+/// Ethereum witness, returning the hex-encoded `r || s || v` signature.
+pub fn sign_revocation(identifier: &[u8; 32], key: &SigningKey) -> String {
+	sign_eth(&revocation_text(identifier), key)
+}
+
+/// Signs the epoch `epoch_id` of `witness` requiring `minimum_witness` signatures, on the chain
+/// with `genesis_hash`, Ethereum-style as the epoch source, returning the hex-encoded
+/// `r || s || v` signature.
+pub fn sign_epoch(
+	genesis_hash: &[u8],
+	epoch_id: u64,
+	witness: &[Witness],
+	minimum_witness: u32,
+	key: &SigningKey,
+) -> String {
+	sign_eth(&epoch_text(genesis_hash, epoch_id, witness, minimum_witness), key)
+}
+
+/// Signs the Ethereum-style hash of `message`, returning the hex-encoded `r || s || v`
+/// signature.
+fn sign_eth(message: &str, key: &SigningKey) -> String {
+	let hash = keccak256_eth(message);
+	let (signature, recovery_id) =
+		key.sign_prehash_recoverable(&hash).expect("hash is 32 bytes; qed");
+	format!("{}{:02x}", hex::encode(signature.to_bytes()), recovery_id.to_byte() + 27)
+}
+
+/// Builds a proof of `claim_info` signed by the witnesses `epoch` selects for it.
+///
+/// The witness set of `epoch` must have been built with `witnesses`.
+pub fn build_proof(epoch: &Epoch, claim_info: ClaimInfo, timestamp: u64) -> Proof {
+	let claim = CompleteClaimData {
+		identifier: claim_info.hash(),
+		owner: String::from(CLAIM_OWNER),
+		epoch: epoch.id,
+		timestampS: timestamp,
+	};
+	let signatures: Vec<String> =
+		fetch_witness_for_claim(epoch.clone(), claim.identifier.clone(), timestamp)
+			.iter()
+			.map(|selected| {
+				let index = epoch
+					.witness
+					.iter()
+					.position(|w| w == selected)
+					.expect("selected witnesses belong to the epoch; qed");
+				sign_claim(&claim, &witness_key(index as u32))
+			})
+			.collect();
+	Proof { claimInfo: claim_info, signedClaim: SignedClaim { claim, signatures } }
+}
@@ -0,0 +1,83 @@
+//! Signature schemes witnesses can sign claims with.
+use crate::{
+	epoch_text, keccak256_eth, recover_signer, revocation_text, traits::WitnessSignatureScheme,
+	CompleteClaimData, Witness,
+};
+use codec::{Decode, DecodeAll, Encode};
+use core::marker::PhantomData;
+use scale_info::prelude::vec::Vec;
+use sp_core::ecdsa;
+use sp_runtime::traits::{IdentifyAccount, Verify};
+
+/// Returns the address of a witness from its encoded public key: the last 20 bytes of its
+/// keccak-256 hash.
+pub fn witness_address(public: &[u8]) -> [u8; 20] {
+	let mut address = [0_u8; 20];
+	address.copy_from_slice(&sp_io::hashing::keccak_256(public)[12..]);
+	address
+}
+
+/// Witnesses signing Ethereum-style: a secp256k1 `r || s || v` signature over the keccak-256
+/// hash of the prefixed claim, from which the witness address is recovered.
+pub struct EthereumWitnesses;
+
+impl WitnessSignatureScheme<ecdsa::Signature, ecdsa::Public> for EthereumWitnesses {
+	fn message(claim: &CompleteClaimData) -> Vec<u8> {
+		keccak256_eth(claim.serialise().as_str())
+	}
+
+	fn recover_signer(message: &[u8], signature: &str) -> Option<[u8; 20]> {
+		recover_signer(message, signature)?.try_into().ok()
+	}
+
+	fn revocation_message(identifier: &[u8; 32]) -> Vec<u8> {
+		keccak256_eth(&revocation_text(identifier))
+	}
+
+	fn epoch_message(
+		genesis_hash: &[u8],
+		epoch_id: u64,
+		witness: &[Witness],
+		minimum_witness: u32,
+	) -> Vec<u8> {
+		keccak256_eth(&epoch_text(genesis_hash, epoch_id, witness, minimum_witness))
+	}
+}
+
+/// Witnesses signing the serialized claim with any `Verify` signature, e.g. sr25519 or ed25519.
+///
+/// Signatures can't be recovered, so each one is the hex-encoded SCALE encoding of
+/// `(Signature, PublicKey)`. The witness address is derived from the public key with
+/// `witness_address`.
+pub struct SubstrateWitnesses<Signature, PublicKey>(PhantomData<(Signature, PublicKey)>);
+
+impl<Signature, PublicKey> WitnessSignatureScheme<Signature, PublicKey>
+	for SubstrateWitnesses<Signature, PublicKey>
+where
+	Signature: Verify<Signer = PublicKey> + Decode,
+	PublicKey: IdentifyAccount<AccountId = PublicKey> + Encode + Decode,
+{
+	fn message(claim: &CompleteClaimData) -> Vec<u8> {
+		claim.serialise().into_bytes()
+	}
+
+	fn recover_signer(message: &[u8], signature: &str) -> Option<[u8; 20]> {
+		let encoded = hex::decode(signature).ok()?;
+		let (signature, public) =
+			<(Signature, PublicKey)>::decode_all(&mut encoded.as_slice()).ok()?;
+		signature.verify(message, &public).then(|| witness_address(&public.encode()))
+	}
+
+	fn revocation_message(identifier: &[u8; 32]) -> Vec<u8> {
+		revocation_text(identifier).into_bytes()
+	}
+
+	fn epoch_message(
+		genesis_hash: &[u8],
+		epoch_id: u64,
+		witness: &[Witness],
+		minimum_witness: u32,
+	) -> Vec<u8> {
+		epoch_text(genesis_hash, epoch_id, witness, minimum_witness).into_bytes()
+	}
+}
@@ -1,14 +1,30 @@
 use super::*;
 use crate::mock::*;
-use frame_support::assert_ok;
+use frame_support::{
+	assert_err, assert_noop, assert_ok,
+	dispatch::GetDispatchInfo,
+	traits::{
+		Currency, GetStorageVersion, Hooks, OnRuntimeUpgrade, ReservableCurrency, StorageVersion,
+	},
+};
 use frame_system::RawOrigin;
+use sp_core::{
+	offchain::{testing, OffchainDbExt, OffchainWorkerExt, TransactionPoolExt},
+	H256,
+};
+use sp_runtime::{
+	testing::UintAuthorityId,
+	traits::{BlakeTwo256, Hash, SignedExtension},
+	transaction_validity::{InvalidTransaction, TransactionValidityError},
+	DispatchError, DispatchResult,
+};
 
 #[test]
 fn init() {
 	new_test_ext().execute_with(|| {
 		let source_account_id = 1;
 		System::set_block_number(1);
-		assert_ok!(Reclaim::init(RawOrigin::Signed(source_account_id).into()));
+		assert_ok!(Reclaim::init(RawOrigin::Root.into(), source_account_id));
 		System::assert_has_event(Event::ContractInitialized { owner: source_account_id }.into());
 		assert_eq!(
 			Reclaim::reclaim_config(),
@@ -22,7 +38,7 @@ fn should_add_epochs() {
 	new_test_ext().execute_with(|| {
 		let source_account_id = 1;
 		System::set_block_number(1);
-		assert_ok!(Reclaim::init(RawOrigin::Signed(source_account_id).into()));
+		assert_ok!(Reclaim::init(RawOrigin::Root.into(), source_account_id));
 		let addr = hex::decode("244897572368eadf65bfbc5aec98d8e5443a9072").unwrap();
 		let addr_slice = addr.get(0..20).unwrap();
 
@@ -43,10 +59,31 @@ fn should_add_epochs() {
 
 		assert_ok!(Reclaim::add_epoch(
 			RawOrigin::Signed(source_account_id).into(),
-			witnesses,
+			witnesses.clone(),
 			minimum_witness
 		));
-		System::assert_has_event(Event::EpochAdded { epoch_id: 1 }.into());
+		System::assert_has_event(
+			Event::EpochAdded { epoch_id: 1, witnesses, minimum: 1, start: 0, end: 10000 }.into(),
+		);
+		System::assert_has_event(
+			Event::WitnessesChanged { epoch_id: 1, added: vec![addr_normzlized], removed: vec![] }
+				.into(),
+		);
+
+		// Rotating the witnesses reports the addresses that changed
+		assert_ok!(Reclaim::add_epoch(
+			RawOrigin::Signed(source_account_id).into(),
+			proof_builder::witnesses(1),
+			minimum_witness
+		));
+		System::assert_last_event(
+			Event::WitnessesChanged {
+				epoch_id: 2,
+				added: vec![proof_builder::witness(0).address],
+				removed: vec![addr_normzlized],
+			}
+			.into(),
+		);
 	})
 }
 
@@ -55,7 +92,7 @@ fn should_approve_valid_proofs() {
 	new_test_ext().execute_with(|| {
 		let source_account_id = 1;
 		System::set_block_number(1);
-		assert_ok!(Reclaim::init(RawOrigin::Signed(source_account_id).into()));
+		assert_ok!(Reclaim::init(RawOrigin::Root.into(), source_account_id));
 		let addr = hex::decode("244897572368eadf65bfbc5aec98d8e5443a9072").unwrap();
 		let addr_slice = addr.get(0..20).unwrap();
 
@@ -106,5 +143,1261 @@ fn should_approve_valid_proofs() {
 			claim_info,
 			signed_claim
 		));
+		System::assert_last_event(
+			Event::ProofVerified {
+				who: source_account_id,
+				identifier: "0xd1dcfc5338cb588396e44e6449e8c750bd4d76332c7e9440c92383382fced0fd"
+					.to_string(),
+				owner: "0x13239fc6bf3847dfedaf067968141ec0363ca42f".to_string(),
+				provider_hash: Some(
+					hex_literal::hex!(
+						"ffd5f761e0fb207368d9ebf9689f077352ab5d20ae0a2c23584c2cd90fc1b1bf"
+					),
+				),
+				epoch_id: 1,
+				timestamp: 1712174155,
+			}
+			.into(),
+		);
+	})
+}
+
+#[test]
+fn should_transfer_ownership() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(Reclaim::init(RawOrigin::Root.into(), 1));
+		assert_noop!(
+			Reclaim::transfer_ownership(RawOrigin::Signed(2).into(), 2),
+			Error::<Test>::OnlyOwner
+		);
+		assert_ok!(Reclaim::transfer_ownership(RawOrigin::Signed(1).into(), 2));
+		System::assert_last_event(Event::OwnershipTransferred { old_owner: 1, new_owner: 2 }.into());
+		assert_eq!(Reclaim::reclaim_config().unwrap().owner, 2);
+		assert_noop!(
+			Reclaim::add_epoch(RawOrigin::Signed(1).into(), proof_builder::witnesses(1), 1),
+			Error::<Test>::OnlyOwner
+		);
+	})
+}
+
+#[test]
+fn should_check_proofs_before_pool() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(Reclaim::init(RawOrigin::Root.into(), 1));
+		let addr = hex::decode("244897572368eadf65bfbc5aec98d8e5443a9072").unwrap();
+		let witness = Witness { address: addr.try_into().unwrap(), host: [1_u8; 32] };
+		assert_ok!(Reclaim::add_epoch(
+			RawOrigin::Signed(1).into(),
+			BoundedVec::try_from(vec![witness]).unwrap(),
+			1
+		));
+		let claim_info = ClaimInfo {
+			provider: "http".to_string(),
+			parameters: "{\"body\":\"\",\"geoLocation\":\"in\",\"method\":\"GET\",\"responseMatches\":[{\"type\":\"regex\",\"value\":\"_steamid\\\">Steam ID: (?<CLAIM_DATA>.*)</div>\"}],\"responseRedactions\":[{\"jsonPath\":\"\",\"regex\":\"_steamid\\\">Steam ID: (?<CLAIM_DATA>.*)</div>\",\"xPath\":\"id(\\\"responsive_page_template_content\\\")/div[@class=\\\"page_header_ctn\\\"]/div[@class=\\\"page_content\\\"]/div[@class=\\\"youraccount_steamid\\\"]\"}],\"url\":\"https://store.steampowered.com/account/\"}".to_string(),
+			context: "{\"contextAddress\":\"user's address\",\"contextMessage\":\"for acmecorp.com on 1st january\",\"extractedParameters\":{\"CLAIM_DATA\":\"76561199601812329\"},\"providerHash\":\"0xffd5f761e0fb207368d9ebf9689f077352ab5d20ae0a2c23584c2cd90fc1b1bf\"}".to_string(),
+		};
+		let signed_claim = SignedClaim {
+			claim: CompleteClaimData {
+				identifier: "0xd1dcfc5338cb588396e44e6449e8c750bd4d76332c7e9440c92383382fced0fd"
+					.to_string(),
+				owner: "0x13239fc6bf3847dfedaf067968141ec0363ca42f".to_string(),
+				epoch: 1_u64,
+				timestampS: 1712174155_u64,
+			},
+			signatures: vec!["2888485f650f8ed02d18e32dd9a1512ca05feb83fc2cbf2df72fd8aa4246c5ee541fa53875c70eb64d3de9143446229a250c7a762202b7cc289ed31b74b31c811c".to_string()],
+		};
+		let check = CheckReclaimProof::<Test>::new();
+		let info = Default::default();
+
+		let call: RuntimeCall = Call::<Test>::verify_proof {
+			claim_info: claim_info.clone(),
+			signed_claim: signed_claim.clone(),
+		}
+		.into();
+		let valid = check.validate(&1, &call, &info, 0).unwrap();
+		assert_eq!(valid.provides.len(), 1);
+		assert_ok!(check.clone().pre_dispatch(&1, &call, &info, 0));
+
+		// A malformed signature is rejected before entering the pool
+		let mut bad_signature = signed_claim.clone();
+		bad_signature.signatures = vec!["zz".to_string()];
+		let call: RuntimeCall =
+			Call::<Test>::verify_proof { claim_info: claim_info.clone(), signed_claim: bad_signature }
+				.into();
+		assert_eq!(
+			check.validate(&1, &call, &info, 0),
+			Err(TransactionValidityError::Invalid(InvalidTransaction::BadProof))
+		);
+
+		// So is a claim that doesn't match its identifier
+		let mut bad_claim = claim_info;
+		bad_claim.context = "{}".to_string();
+		let call: RuntimeCall = Call::<Test>::verify_proof { claim_info: bad_claim, signed_claim }.into();
+		assert_eq!(
+			check.clone().pre_dispatch(&1, &call, &info, 0),
+			Err(TransactionValidityError::Invalid(InvalidTransaction::BadProof))
+		);
+
+		// Calls without a proof are left untouched
+		let call: RuntimeCall = Call::<Test>::init { owner: 1 }.into();
+		assert_eq!(check.validate(&1, &call, &info, 0), Ok(Default::default()));
+	})
+}
+
+// Serves an epoch of the first generated witness, signed by the generated key at `signer`, and
+// runs the offchain worker against it. Returns the transactions it submitted.
+fn sync_epoch_signed_by(signer: u32) -> Vec<Extrinsic> {
+	let (offchain, offchain_state) = testing::TestOffchainExt::new();
+	let (pool, pool_state) = testing::TestTransactionPoolExt::new();
+	let mut t = new_test_ext();
+	t.register_extension(OffchainWorkerExt::new(offchain.clone()));
+	t.register_extension(OffchainDbExt::new(offchain));
+	t.register_extension(TransactionPoolExt::new(pool));
+
+	UintAuthorityId::set_all_keys(vec![7_u64]);
+
+	t.execute_with(|| {
+		let witness = proof_builder::witness(0);
+		let signature = proof_builder::sign_epoch(
+			System::block_hash(0).as_ref(),
+			1,
+			&[witness.clone()],
+			1,
+			&proof_builder::witness_key(signer),
+		);
+		let body = format!(
+			"{{\"minimumWitnessesForClaimCreation\":1,\"witnesses\":[{{\"address\":\"0x{}\",\"host\":\"0x{}\"}}],\"signature\":\"{}\"}}",
+			hex::encode(witness.address),
+			hex::encode(witness.host),
+			signature
+		);
+		offchain_state.write().expect_request(testing::PendingRequest {
+			method: "GET".into(),
+			uri: EPOCH_SOURCE_URL.into(),
+			response: Some(body.into_bytes()),
+			sent: true,
+			..Default::default()
+		});
+
+		let source_account_id = 1;
+		System::set_block_number(1);
+		assert_ok!(Reclaim::init(RawOrigin::Root.into(), source_account_id));
+		assert_ok!(Reclaim::set_epoch_syncers(
+			RawOrigin::Signed(source_account_id).into(),
+			BoundedVec::try_from(vec![7]).unwrap()
+		));
+
+		Reclaim::offchain_worker(10);
+	});
+	let transactions = pool_state.write().transactions.clone();
+	transactions.iter().map(|tx| Extrinsic::decode(&mut &tx[..]).unwrap()).collect()
+}
+
+#[test]
+fn should_submit_sync_epoch_from_source() {
+	let transactions = sync_epoch_signed_by(proof_builder::EPOCH_SOURCE_KEY);
+	let signature = new_test_ext().execute_with(|| signed_epoch(&proof_builder::witnesses(1), 1));
+	assert_eq!(transactions.len(), 1);
+	assert_eq!(transactions[0].signature, Some((7, ())));
+	assert_eq!(
+		transactions[0].call,
+		RuntimeCall::Reclaim(crate::Call::sync_epoch {
+			witness: BoundedVec::try_from(vec![proof_builder::witness(0)]).unwrap(),
+			minimum_witness: 1,
+			signature,
+		})
+	);
+}
+
+#[test]
+fn should_not_sync_epoch_signed_by_unknown_key() {
+	assert!(sync_epoch_signed_by(proof_builder::EPOCH_SOURCE_KEY + 1).is_empty());
+}
+
+// Signature by the epoch source of the next epoch of the chain, of `witness` requiring
+// `minimum_witness` signatures.
+fn signed_epoch(witness: &[Witness], minimum_witness: u32) -> String {
+	let next_epoch = Reclaim::reclaim_config().map_or(1, |config| config.current_epoch + 1);
+	signed_epoch_for(System::block_hash(0), next_epoch, witness, minimum_witness)
+}
+
+// Signature by the epoch source of the epoch `epoch_id` on the chain with `genesis_hash`.
+fn signed_epoch_for(
+	genesis_hash: H256,
+	epoch_id: u64,
+	witness: &[Witness],
+	minimum_witness: u32,
+) -> String {
+	let key = proof_builder::witness_key(proof_builder::EPOCH_SOURCE_KEY);
+	proof_builder::sign_epoch(genesis_hash.as_ref(), epoch_id, witness, minimum_witness, &key)
+}
+
+#[test]
+fn should_reject_sync_epoch_from_non_syncer() {
+	new_test_ext().execute_with(|| {
+		let source_account_id = 1;
+		System::set_block_number(1);
+		assert_ok!(Reclaim::init(RawOrigin::Root.into(), source_account_id));
+		let witness = proof_builder::witnesses(1);
+		let signature = signed_epoch(&witness, 1);
+		assert_noop!(
+			Reclaim::sync_epoch(RawOrigin::Signed(7).into(), witness, 1, signature),
+			Error::<Test>::OnlyEpochSyncer
+		);
+	})
+}
+
+#[test]
+fn should_sync_epoch_signed_by_epoch_source() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(Reclaim::init(RawOrigin::Root.into(), 1));
+		let witness = proof_builder::witnesses(2);
+
+		// Signed by another key, or for another minimum
+		let key = proof_builder::witness_key(proof_builder::EPOCH_SOURCE_KEY + 1);
+		let unknown_signer =
+			proof_builder::sign_epoch(System::block_hash(0).as_ref(), 1, &witness, 1, &key);
+		assert_noop!(
+			Reclaim::sync_epoch(RawOrigin::Signed(1).into(), witness.clone(), 1, unknown_signer),
+			Error::<Test>::InvalidEpochSignature
+		);
+		let other_minimum = signed_epoch(&witness, 2);
+		assert_noop!(
+			Reclaim::sync_epoch(RawOrigin::Signed(1).into(), witness.clone(), 1, other_minimum),
+			Error::<Test>::InvalidEpochSignature
+		);
+		assert_noop!(
+			Reclaim::sync_epoch(RawOrigin::Signed(1).into(), witness.clone(), 1, "0x".into()),
+			Error::<Test>::InvalidEpochSignature
+		);
+
+		let signature = signed_epoch(&witness, 1);
+		assert_ok!(Reclaim::sync_epoch(
+			RawOrigin::Signed(1).into(),
+			witness.clone(),
+			1,
+			signature.clone()
+		));
+		assert_eq!(Reclaim::reclaim_config().unwrap().current_epoch, 1);
+
+		// Syncing the current epoch again adds no epoch
+		assert_noop!(
+			Reclaim::sync_epoch(RawOrigin::Signed(1).into(), witness.clone(), 1, signature),
+			Error::<Test>::EpochUnchanged
+		);
+		let signature = signed_epoch(&witness, 2);
+		assert_ok!(Reclaim::sync_epoch(RawOrigin::Signed(1).into(), witness, 2, signature));
+		assert_eq!(Reclaim::reclaim_config().unwrap().current_epoch, 2);
+	})
+}
+
+#[test]
+fn should_reject_replayed_epoch_signatures() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(Reclaim::init(RawOrigin::Root.into(), 1));
+		let rotated_out = proof_builder::witnesses(2);
+		let replayed = signed_epoch(&rotated_out, 1);
+		assert_ok!(Reclaim::sync_epoch(
+			RawOrigin::Signed(1).into(),
+			rotated_out.clone(),
+			1,
+			replayed.clone()
+		));
+		let witness = proof_builder::witnesses(3);
+		let signature = signed_epoch(&witness, 2);
+		assert_ok!(Reclaim::sync_epoch(RawOrigin::Signed(1).into(), witness, 2, signature));
+
+		// The signature of a rotated out epoch is bound to its id
+		assert_noop!(
+			Reclaim::sync_epoch(RawOrigin::Signed(1).into(), rotated_out.clone(), 1, replayed),
+			Error::<Test>::InvalidEpochSignature
+		);
+
+		// Nor can an epoch signed for another chain be synced
+		let other_chain = signed_epoch_for(H256::repeat_byte(1), 3, &rotated_out, 1);
+		assert_noop!(
+			Reclaim::sync_epoch(RawOrigin::Signed(1).into(), rotated_out, 1, other_chain),
+			Error::<Test>::InvalidEpochSignature
+		);
+	})
+}
+
+#[test]
+fn should_parse_claim_context() {
+	let claim_info = ClaimInfo {
+		provider: "http".to_string(),
+		parameters: "{}".to_string(),
+		context: "{\"contextAddress\":\"user's address\",\"contextMessage\":\"for acmecorp.com on 1st january\",\"extractedParameters\":{\"CLAIM_DATA\":\"76561199601812329\"},\"providerHash\":\"0xffd5f761e0fb207368d9ebf9689f077352ab5d20ae0a2c23584c2cd90fc1b1bf\"}".to_string(),
+	};
+	let context = claim_info.parse_context().unwrap();
+	assert_eq!(context.context_address, "user's address");
+	assert_eq!(
+		context.extracted_parameters.get("CLAIM_DATA"),
+		Some(&"76561199601812329".to_string())
+	);
+	assert_eq!(
+		claim_info.provider_hash().unwrap().to_vec(),
+		hex::decode("ffd5f761e0fb207368d9ebf9689f077352ab5d20ae0a2c23584c2cd90fc1b1bf").unwrap()
+	);
+}
+
+#[test]
+fn should_charge_only_checks_performed() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(Reclaim::init(RawOrigin::Root.into(), 1));
+		assert_ok!(Reclaim::add_epoch(RawOrigin::Signed(1).into(), proof_builder::witnesses(5), 3));
+		let proof = epoch_proof("");
+
+		// A valid proof recovers every signature
+		let mut progress = ProofCheckProgress::default();
+		assert_ok!(Reclaim::check_proof(&proof, &mut progress));
+		assert_eq!(progress, ProofCheckProgress { witnesses: 5, signatures: 3 });
+
+		// A bad second signature stops the check before the third one is recovered
+		let mut forged = proof.clone();
+		forged.signedClaim.signatures[1] =
+			proof_builder::sign_claim(&forged.signedClaim.claim, &proof_builder::witness_key(99));
+		let mut progress = ProofCheckProgress::default();
+		assert!(matches!(
+			Reclaim::check_proof(&forged, &mut progress),
+			Err(Error::<Test>::SignatureMismatch)
+		));
+		assert_eq!(progress.signatures, 2);
+
+		// A hash mismatch is caught before any recovery, and only that work is charged
+		let mut mismatched = proof;
+		mismatched.claimInfo.parameters = "{\"changed\":true}".to_string();
+		let error = Reclaim::verify_proof(
+			RawOrigin::Signed(1).into(),
+			mismatched.claimInfo.clone(),
+			mismatched.signedClaim.clone(),
+		)
+		.unwrap_err();
+		assert_eq!(error.error, Error::<Test>::HashMismatch.into());
+		assert_eq!(
+			error.post_info.actual_weight,
+			Some(Reclaim::proof_check_weight(
+				&mismatched,
+				ProofCheckProgress { witnesses: 5, signatures: 0 }
+			))
+		);
+	})
+}
+
+#[test]
+fn should_verify_proof_batches() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(Reclaim::init(RawOrigin::Root.into(), 1));
+		assert_ok!(Reclaim::add_epoch(RawOrigin::Signed(1).into(), proof_builder::witnesses(5), 2));
+		let mut forged = epoch_proof("forged");
+		forged.signedClaim.signatures[0] =
+			proof_builder::sign_claim(&forged.signedClaim.claim, &proof_builder::witness_key(99));
+		let proofs: BoundedVec<Proof, _> =
+			BoundedVec::truncate_from(vec![epoch_proof("first"), forged, epoch_proof("second")]);
+
+		// All or nothing fails on the invalid proof, charging the checks done up to it
+		let error = Reclaim::verify_proofs(
+			RawOrigin::Signed(1).into(),
+			proofs.clone(),
+			BatchMode::AllOrNothing,
+		)
+		.unwrap_err();
+		assert_eq!(error.error, Error::<Test>::SignatureMismatch.into());
+		assert_eq!(
+			error.post_info.actual_weight,
+			Some(
+				Reclaim::proof_check_weight(
+					&proofs[0],
+					ProofCheckProgress { witnesses: 5, signatures: 2 }
+				)
+				.saturating_add(Reclaim::proof_check_weight(
+					&proofs[1],
+					ProofCheckProgress { witnesses: 5, signatures: 1 }
+				))
+			)
+		);
+
+		// Best effort reports the invalid proof and verifies the others
+		System::reset_events();
+		assert_ok!(Reclaim::verify_proofs(
+			RawOrigin::Signed(1).into(),
+			proofs,
+			BatchMode::BestEffort
+		));
+		System::assert_has_event(
+			Event::ProofRejected { index: 1, error: Error::<Test>::SignatureMismatch.into() }
+				.into(),
+		);
+		System::assert_last_event(Event::BatchVerified { verified: 2, rejected: 1 }.into());
+		assert_eq!(
+			System::events()
+				.iter()
+				.filter(|record| matches!(
+					record.event,
+					RuntimeEvent::Reclaim(Event::ProofVerified { epoch_id: 1, .. })
+				))
+				.count(),
+			2
+		);
+	})
+}
+
+#[test]
+fn should_recover_signer_with_host_functions() {
+	new_test_ext().execute_with(|| {
+		let claim = CompleteClaimData {
+			identifier: "0xd1dcfc5338cb588396e44e6449e8c750bd4d76332c7e9440c92383382fced0fd"
+				.to_string(),
+			owner: proof_builder::CLAIM_OWNER.to_string(),
+			epoch: 1_u64,
+			timestampS: 1712174155_u64,
+		};
+		let signature = proof_builder::sign_claim(&claim, &proof_builder::witness_key(0));
+		let message_hash = keccak256_eth(&claim.serialise());
+		let address = proof_builder::witness(0).address.to_vec();
+
+		// The host functions and the in-runtime fallback agree
+		assert_eq!(recover_signer(&message_hash, &signature), Some(address.clone()));
+		assert_eq!(recover_signer_in_runtime(&message_hash, &signature), Some(address));
+
+		// Only Ethereum-style recovery ids are accepted
+		let mut raw = hex::decode(&signature).unwrap();
+		raw[64] -= 27;
+		assert_eq!(recover_signer(&message_hash, &hex::encode(raw)), None);
+	})
+}
+
+#[test]
+fn should_verify_substrate_witness_signatures() {
+	use schemes::{witness_address, SubstrateWitnesses};
+	use sp_core::{sr25519, Pair};
+	type Scheme = SubstrateWitnesses<sr25519::Signature, sr25519::Public>;
+
+	let claim = CompleteClaimData {
+		identifier: "0xd1dcfc5338cb588396e44e6449e8c750bd4d76332c7e9440c92383382fced0fd".to_string(),
+		owner: proof_builder::CLAIM_OWNER.to_string(),
+		epoch: 1_u64,
+		timestampS: 1712174155_u64,
+	};
+	let pair = sr25519::Pair::from_seed(&[7_u8; 32]);
+	let message = Scheme::message(&claim);
+	let signature = hex::encode((pair.sign(&message), pair.public()).encode());
+
+	assert_eq!(
+		Scheme::recover_signer(&message, &signature),
+		Some(witness_address(&pair.public().encode()))
+	);
+	// A signature over another claim doesn't verify
+	let mut other = claim;
+	other.epoch = 2;
+	assert_eq!(Scheme::recover_signer(&Scheme::message(&other), &signature), None);
+	// Neither does a signature without its public key
+	assert_eq!(Scheme::recover_signer(&message, &hex::encode(pair.sign(&message).encode())), None);
+}
+
+#[test]
+fn should_migrate_unversioned_storage_to_v1() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Reclaim::init(RawOrigin::Root.into(), 1));
+		assert_ok!(Reclaim::add_epoch(RawOrigin::Signed(1).into(), proof_builder::witnesses(1), 1));
+		StorageVersion::new(0).put::<Reclaim>();
+
+		migrations::v1::MigrateToV1::<Test>::on_runtime_upgrade();
+		assert_eq!(Reclaim::on_chain_storage_version(), 1);
+		assert_eq!(Reclaim::epochs(1).unwrap().witness, proof_builder::witnesses(1));
+
+		// Running the migration again leaves the storage untouched
+		migrations::v1::MigrateToV1::<Test>::on_runtime_upgrade();
+		assert_eq!(Reclaim::on_chain_storage_version(), 1);
+		assert_eq!(Reclaim::epochs(1).unwrap().witness, proof_builder::witnesses(1));
+	})
+}
+
+#[test]
+fn should_check_storage_invariants() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Reclaim::do_try_state());
+		assert_ok!(Reclaim::init(RawOrigin::Root.into(), 1));
+		assert_ok!(Reclaim::add_epoch(RawOrigin::Signed(1).into(), proof_builder::witnesses(3), 2));
+		assert_ok!(Reclaim::add_epoch(RawOrigin::Signed(1).into(), proof_builder::witnesses(2), 1));
+		assert_ok!(Reclaim::do_try_state());
+
+		// A gap in the epoch ids
+		let epoch = Reclaim::epochs(2).unwrap();
+		Epochs::<Test>::remove(1);
+		assert_err!(
+			Reclaim::do_try_state(),
+			"Epoch ids are not contiguous up to the current epoch"
+		);
+
+		// An epoch requiring more witnesses than it has
+		Epochs::<Test>::insert(
+			1,
+			Epoch { id: 1, minimum_witness_for_claim_creation: 4, ..epoch.clone() },
+		);
+		assert_err!(Reclaim::do_try_state(), "Epoch requires more witnesses than it has");
+
+		// An epoch listing the same witness twice
+		let duplicated = BoundedVec::truncate_from(vec![proof_builder::witness(0); 2]);
+		Epochs::<Test>::insert(1, Epoch { id: 1, witness: duplicated, ..epoch });
+		assert_err!(Reclaim::do_try_state(), "Epoch has duplicate witness addresses");
+	})
+}
+
+#[test]
+fn should_migrate_epoch_minimum_to_u32() {
+	new_test_ext().execute_with(|| {
+		StorageVersion::new(1).put::<Reclaim>();
+		let old_epoch = migrations::v2::OldEpoch {
+			id: 1,
+			timestamp_start: 0,
+			timestamp_end: 10000,
+			minimum_witness_for_claim_creation: 2,
+			witness: proof_builder::witnesses(3),
+		};
+		frame_support::storage::unhashed::put(&Epochs::<Test>::hashed_key_for(1), &old_epoch);
+
+		migrations::v2::MigrateToV2::<Test>::on_runtime_upgrade();
+		assert_eq!(Reclaim::on_chain_storage_version(), 2);
+		assert_eq!(
+			Reclaim::epochs(1),
+			Some(Epoch {
+				id: 1,
+				timestamp_start: 0,
+				timestamp_end: 10000,
+				minimum_witness_for_claim_creation: 2,
+				witness: proof_builder::witnesses(3),
+			})
+		);
+	})
+}
+
+#[test]
+fn should_reject_invalid_epochs() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Reclaim::init(RawOrigin::Root.into(), 1));
+		assert_noop!(
+			Reclaim::add_epoch(RawOrigin::Signed(1).into(), BoundedVec::default(), 1),
+			Error::<Test>::EmptyWitnessSet
+		);
+		assert_noop!(
+			Reclaim::add_epoch(RawOrigin::Signed(1).into(), proof_builder::witnesses(3), 0),
+			Error::<Test>::InvalidMinimumWitness
+		);
+		assert_noop!(
+			Reclaim::add_epoch(RawOrigin::Signed(1).into(), proof_builder::witnesses(3), 4),
+			Error::<Test>::InvalidMinimumWitness
+		);
+		let duplicated = BoundedVec::truncate_from(vec![proof_builder::witness(0); 2]);
+		assert_noop!(
+			Reclaim::add_epoch(RawOrigin::Signed(1).into(), duplicated, 1),
+			Error::<Test>::DuplicateWitness
+		);
+		let signature = signed_epoch(&proof_builder::witnesses(3), 0);
+		assert_noop!(
+			Reclaim::sync_epoch(
+				RawOrigin::Signed(1).into(),
+				proof_builder::witnesses(3),
+				0,
+				signature
+			),
+			Error::<Test>::InvalidMinimumWitness
+		);
+		assert_ok!(Reclaim::add_epoch(RawOrigin::Signed(1).into(), proof_builder::witnesses(3), 3));
+	})
+}
+
+#[test]
+fn should_not_select_witnesses_of_empty_epochs() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Reclaim::init(RawOrigin::Root.into(), 1));
+		assert_ok!(Reclaim::add_epoch(RawOrigin::Signed(1).into(), proof_builder::witnesses(1), 1));
+		let proof = epoch_proof("");
+
+		// An epoch left without witnesses by a migration
+		let epoch = Epoch { witness: BoundedVec::default(), ..Reclaim::epochs(1).unwrap() };
+		Epochs::<Test>::insert(1, epoch.clone());
+		let claim = &proof.signedClaim.claim;
+		let selected = fetch_witness_for_claim(epoch, claim.identifier.clone(), claim.timestampS);
+		assert!(selected.is_empty());
+		assert_noop!(verify_as(1, &proof), Error::<Test>::EmptyWitnessSet);
+	})
+}
+
+#[test]
+fn should_fail_before_init_and_epochs() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(Reclaim::init(RawOrigin::Signed(1).into(), 1), DispatchError::BadOrigin);
+		assert_noop!(
+			Reclaim::add_epoch(RawOrigin::Signed(1).into(), proof_builder::witnesses(1), 1),
+			Error::<Test>::NotInitialized
+		);
+		let epoch = Epoch {
+			id: 1,
+			timestamp_start: 0,
+			timestamp_end: 10000,
+			minimum_witness_for_claim_creation: 1,
+			witness: proof_builder::witnesses(1),
+		};
+		let proof = proof_builder::build_proof(&epoch, proof_builder::http_claim(""), 1712174155);
+		assert_noop!(verify_as(1, &proof), Error::<Test>::NotInitialized);
+
+		// Initialized, but without any epoch yet
+		assert_ok!(Reclaim::init(RawOrigin::Root.into(), 1));
+		assert_eq!(Reclaim::epochs(0), None);
+		assert_noop!(
+			Reclaim::verify_proof(RawOrigin::Signed(1).into(), proof.claimInfo, proof.signedClaim),
+			Error::<Test>::EpochNotFound
+		);
+	})
+}
+
+#[test]
+fn should_pause_verification() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(Reclaim::init(RawOrigin::Root.into(), 1));
+		assert_ok!(Reclaim::add_epoch(RawOrigin::Signed(1).into(), proof_builder::witnesses(3), 2));
+		let proof = epoch_proof("");
+		let provider_hash = proof.claimInfo.provider_hash().unwrap();
+
+		assert_noop!(
+			Reclaim::pause(RawOrigin::Signed(1).into(), PauseScope::All),
+			DispatchError::BadOrigin
+		);
+		for scope in [PauseScope::All, PauseScope::Epoch(1), PauseScope::Provider(provider_hash)] {
+			assert_ok!(Reclaim::pause(RawOrigin::Root.into(), scope));
+			System::assert_last_event(Event::VerificationPaused { scope }.into());
+			assert!(Reclaim::is_paused(scope));
+			assert_noop!(
+				Reclaim::pause(RawOrigin::Root.into(), scope),
+				Error::<Test>::AlreadyPaused
+			);
+			assert_noop!(verify_as(1, &proof), Error::<Test>::Paused);
+			assert_ok!(Reclaim::unpause(RawOrigin::Root.into(), scope));
+			System::assert_last_event(Event::VerificationUnpaused { scope }.into());
+		}
+
+		// Pauses of other epochs or providers leave the proof verifiable
+		assert_ok!(Reclaim::pause(RawOrigin::Root.into(), PauseScope::Epoch(2)));
+		assert_ok!(Reclaim::pause(RawOrigin::Root.into(), PauseScope::Provider([0_u8; 32])));
+		assert_ok!(verify_as(1, &proof));
+		assert_noop!(
+			Reclaim::unpause(RawOrigin::Root.into(), PauseScope::All),
+			Error::<Test>::NotPaused
+		);
+	})
+}
+
+#[test]
+fn should_refuse_revoked_witnesses() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(Reclaim::init(RawOrigin::Root.into(), 1));
+		assert_ok!(Reclaim::add_epoch(RawOrigin::Signed(1).into(), proof_builder::witnesses(3), 2));
+		let proof = epoch_proof("");
+		let attesting = Reclaim::attesting_witnesses(&proof);
+		assert_eq!(attesting.len(), 2);
+		let bystander = Reclaim::epochs(1)
+			.unwrap()
+			.witness
+			.iter()
+			.map(|witness| witness.address)
+			.find(|address| !attesting.contains(address))
+			.unwrap();
+
+		assert_noop!(
+			Reclaim::revoke_witness(RawOrigin::Signed(1).into(), bystander),
+			DispatchError::BadOrigin
+		);
+		// Revoking a witness that did not sign leaves the proof verifiable
+		assert_ok!(Reclaim::revoke_witness(RawOrigin::Root.into(), bystander));
+		System::assert_last_event(Event::WitnessRevoked { address: bystander }.into());
+		assert_eq!(Reclaim::revoked_witnesses(bystander), Some(1));
+		assert_ok!(verify_as(1, &proof));
+		assert_noop!(
+			Reclaim::revoke_witness(RawOrigin::Root.into(), bystander),
+			Error::<Test>::AlreadyRevoked
+		);
+
+		assert_ok!(Reclaim::revoke_witness(RawOrigin::Root.into(), attesting[1]));
+		assert_noop!(verify_as(1, &proof), Error::<Test>::RevokedWitness);
+
+		// Without a verifier, proofs are rejected instead of panicking
+		type NoVerifier = ();
+		assert!(<NoVerifier as ReclaimVerifier<u64, Proof>>::verify_proof(&1, &proof).is_err());
+		assert!(<NoVerifier as ReclaimVerifier<u64, Proof>>::validate_proof(&1, &proof).is_err());
+		assert!(
+			<NoVerifier as ReclaimVerifier<u64, Proof>>::attesting_witnesses(&proof).is_empty()
+		);
+		assert!(!<NoVerifier as ReclaimVerifier<u64, Proof>>::is_witness_revoked(&attesting[1]));
+		assert!(!<NoVerifier as ReclaimVerifier<u64, Proof>>::is_claim_revoked(&[0_u8; 32]));
+	})
+}
+
+#[test]
+fn should_rate_limit_proof_submissions() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(Reclaim::init(RawOrigin::Root.into(), 1));
+		assert_ok!(Reclaim::add_epoch(RawOrigin::Signed(1).into(), proof_builder::witnesses(3), 2));
+		let proof = epoch_proof("");
+		let check = CheckReclaimProof::<Test>::new();
+		let info = Default::default();
+		let call: RuntimeCall = Call::<Test>::verify_proof {
+			claim_info: proof.claimInfo.clone(),
+			signed_claim: proof.signedClaim.clone(),
+		}
+		.into();
+		let submit = |result: DispatchResult| {
+			let pre = check.clone().pre_dispatch(&1, &call, &info, 0).unwrap();
+			assert_eq!(pre, Some(1));
+			assert_ok!(CheckReclaimProof::<Test>::post_dispatch(
+				Some(pre),
+				&info,
+				&Default::default(),
+				0,
+				&result
+			));
+		};
+		let rate_limited = Err(TransactionValidityError::Invalid(InvalidTransaction::Custom(
+			RATE_LIMITED,
+		)));
+
+		// The second consecutive failure counts twice, using up the 3 attempts of the window
+		submit(Err(Error::<Test>::SignatureMismatch.into()));
+		submit(Err(Error::<Test>::SignatureMismatch.into()));
+		assert_eq!(
+			Reclaim::proof_attempts(1),
+			Some(ProofAttempts { window_start: 1, attempts: 3, failures: 2 })
+		);
+		assert_eq!(check.validate(&1, &call, &info, 0), rate_limited);
+		assert_noop!(verify_as(1, &proof), Error::<Test>::RateLimited);
+		assert_noop!(
+			Reclaim::verify_proofs(
+				RawOrigin::Signed(1).into(),
+				BoundedVec::truncate_from(vec![proof.clone()]),
+				BatchMode::BestEffort
+			),
+			Error::<Test>::RateLimited
+		);
+		// Other accounts keep their own limit
+		assert!(check.validate(&2, &call, &info, 0).is_ok());
+
+		// A new window starts with no attempts, but failures keep escalating
+		System::set_block_number(11);
+		assert!(check.validate(&1, &call, &info, 0).is_ok());
+		submit(Err(Error::<Test>::SignatureMismatch.into()));
+		assert_eq!(
+			Reclaim::proof_attempts(1),
+			Some(ProofAttempts { window_start: 11, attempts: 4, failures: 3 })
+		);
+		assert_eq!(check.validate(&1, &call, &info, 0), rate_limited);
+
+		// Successful submissions are counted by the pallet, and clear the failures
+		System::set_block_number(21);
+		submit(Ok(()));
+		assert_eq!(Reclaim::proof_attempts(1).unwrap().attempts, 4);
+		assert_ok!(verify_as(1, &proof));
+		assert_eq!(
+			Reclaim::proof_attempts(1),
+			Some(ProofAttempts { window_start: 21, attempts: 1, failures: 0 })
+		);
+	})
+}
+
+#[test]
+fn should_count_each_proof_of_a_batch() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(Reclaim::init(RawOrigin::Root.into(), 1));
+		assert_ok!(Reclaim::add_epoch(RawOrigin::Signed(1).into(), proof_builder::witnesses(3), 2));
+		let mut forged = epoch_proof("");
+		forged.signedClaim.signatures[0] =
+			proof_builder::sign_claim(&forged.signedClaim.claim, &proof_builder::witness_key(99));
+		let batch = |proofs: Vec<Proof>| {
+			Reclaim::verify_proofs(
+				RawOrigin::Signed(1).into(),
+				BoundedVec::truncate_from(proofs),
+				BatchMode::BestEffort,
+			)
+		};
+
+		// A batch of rejected proofs fails once per proof, escalating like separate calls
+		assert_ok!(batch(vec![forged.clone(), forged.clone()]));
+		System::assert_last_event(Event::BatchVerified { verified: 0, rejected: 2 }.into());
+		assert_eq!(
+			Reclaim::proof_attempts(1),
+			Some(ProofAttempts { window_start: 1, attempts: 3, failures: 2 })
+		);
+		assert_noop!(batch(vec![epoch_proof("")]), Error::<Test>::RateLimited);
+
+		// Proofs beyond the limit are rejected unchecked, without counting as failures
+		System::set_block_number(11);
+		assert_ok!(batch(vec![forged, epoch_proof("first"), epoch_proof("second")]));
+		System::assert_has_event(
+			Event::ProofRejected { index: 0, error: Error::<Test>::SignatureMismatch.into() }
+				.into(),
+		);
+		for index in [1, 2] {
+			System::assert_has_event(
+				Event::ProofRejected { index, error: Error::<Test>::RateLimited.into() }.into(),
+			);
+		}
+		assert_eq!(
+			Reclaim::proof_attempts(1),
+			Some(ProofAttempts { window_start: 11, attempts: 4, failures: 3 })
+		);
+	})
+}
+
+#[test]
+fn should_store_verified_claims() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		VerifiedClaimDeposit::set(Some(10));
+		Balances::make_free_balance_be(&1, 100);
+		Balances::make_free_balance_be(&3, 5);
+		assert_ok!(Reclaim::init(RawOrigin::Root.into(), 1));
+		assert_ok!(Reclaim::add_epoch(RawOrigin::Signed(1).into(), proof_builder::witnesses(3), 2));
+		let proof = epoch_proof("76561199601812329");
+		let identifier = claim_identifier(&proof);
+
+		// Submitters without the deposit can't verify a claim that isn't stored yet
+		assert!(verify_as(3, &proof).is_err());
+		assert_ok!(verify_as(1, &proof));
+		let context = proof.claimInfo.parse_context().unwrap();
+		let owner = hex::decode(&proof.signedClaim.claim.owner[2..]).unwrap();
+		assert_eq!(
+			Reclaim::verified_claims(identifier),
+			Some(VerifiedClaim {
+				submitter: 1,
+				owner: BoundedVec::truncate_from(owner),
+				epoch: 1,
+				block: 1,
+				provider_hash: proof.claimInfo.provider_hash(),
+				parameters_hash: BlakeTwo256::hash_of(&context.extracted_parameters),
+				deposit: 10,
+			})
+		);
+		assert_eq!(Balances::reserved_balance(1), 10);
+
+		// Verifying a stored claim again reserves nothing
+		assert_ok!(verify_as(3, &proof));
+		assert_eq!(Balances::reserved_balance(3), 0);
+		assert_eq!(Reclaim::verified_claims(identifier).unwrap().submitter, 1);
+
+		assert_noop!(
+			Reclaim::remove_verified_claim(RawOrigin::Signed(3).into(), identifier),
+			Error::<Test>::NotClaimSubmitter
+		);
+		assert_ok!(Reclaim::remove_verified_claim(RawOrigin::Signed(1).into(), identifier));
+		System::assert_last_event(Event::VerifiedClaimRemoved { identifier }.into());
+		assert_eq!(Reclaim::verified_claims(identifier), None);
+		assert_eq!(Balances::reserved_balance(1), 0);
+		assert_noop!(
+			Reclaim::remove_verified_claim(RawOrigin::Signed(1).into(), identifier),
+			Error::<Test>::ClaimNotFound
+		);
+	})
+}
+
+#[test]
+fn should_revoke_claims() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		VerifiedClaimDeposit::set(Some(10));
+		Balances::make_free_balance_be(&1, 100);
+		assert_ok!(Reclaim::init(RawOrigin::Root.into(), 1));
+		assert_ok!(Reclaim::add_epoch(RawOrigin::Signed(1).into(), proof_builder::witnesses(3), 2));
+		let proof = epoch_proof("");
+		let identifier = claim_identifier(&proof);
+		let attesting = attesting_indices(&proof, 3);
+		assert_eq!(attesting.len(), 2);
+		let bystander = (0..3).find(|index| !attesting.contains(index)).unwrap();
+		let revoke = |indices: &[u32]| {
+			Reclaim::revoke_claim(
+				RawOrigin::Signed(2).into(),
+				identifier,
+				revocation_signed_by(&identifier, indices),
+			)
+		};
+
+		assert_noop!(revoke(&attesting), Error::<Test>::ClaimNotFound);
+		assert_ok!(verify_as(1, &proof));
+		// The attestation outlives the stored claim, which its submitter may remove
+		assert_ok!(Reclaim::remove_verified_claim(RawOrigin::Signed(1).into(), identifier));
+		assert_eq!(Reclaim::claim_witnesses(&identifier), Reclaim::attesting_witnesses(&proof));
+
+		// Only every attesting witness, each counted once however often it signs, can revoke
+		// the claim, not other witnesses of the epoch
+		let first = attesting[0];
+		for signers in [vec![first], vec![first, first], vec![first, bystander]] {
+			assert_noop!(revoke(&signers), Error::<Test>::UnauthorizedRevocation);
+		}
+		assert_ok!(revoke(&[attesting[1], first]));
+		System::assert_last_event(Event::ClaimRevoked { identifier }.into());
+		assert_eq!(Reclaim::revoked_claims(identifier), Some(1));
+		assert_noop!(revoke(&attesting), Error::<Test>::ClaimAlreadyRevoked);
+
+		// A revoked claim can't be verified again
+		assert_noop!(verify_as(1, &proof), Error::<Test>::RevokedClaim);
+
+		// Revoked witnesses are no longer needed, and claims stored without deposit are revoked
+		// the same way
+		VerifiedClaimDeposit::set(None);
+		let other = epoch_proof("second");
+		let other_identifier = claim_identifier(&other);
+		assert_ok!(verify_as(1, &other));
+		assert_eq!(Reclaim::verified_claims(other_identifier), None);
+		let other_attesting = attesting_indices(&other, 3);
+		assert_eq!(other_attesting.len(), 2);
+		assert_ok!(Reclaim::revoke_witness(
+			RawOrigin::Root.into(),
+			proof_builder::witness(other_attesting[0]).address
+		));
+		assert_ok!(Reclaim::revoke_claim(
+			RawOrigin::Signed(2).into(),
+			other_identifier,
+			revocation_signed_by(&other_identifier, &other_attesting[1..])
+		));
+		assert!(Reclaim::is_claim_revoked(&other_identifier));
+
+		// The owner of a claim revokes it alone
+		let owner_key = proof_builder::witness_key(10);
+		let owned_identifier = [2_u8; 32];
+		ClaimAttestations::<Test>::insert(
+			owned_identifier,
+			ClaimAttestation {
+				claim: VerifiedClaim {
+					submitter: 1,
+					owner: BoundedVec::truncate_from(proof_builder::address(&owner_key).to_vec()),
+					epoch: 1,
+					block: 1,
+					provider_hash: None,
+					parameters_hash: Default::default(),
+					deposit: 0,
+				},
+				timestamp: 1712174155,
+			},
+		);
+		let signed_by_owner = |key| {
+			BoundedVec::truncate_from(vec![proof_builder::sign_revocation(&owned_identifier, key)])
+		};
+		assert_noop!(
+			Reclaim::revoke_claim(
+				RawOrigin::Signed(2).into(),
+				owned_identifier,
+				signed_by_owner(&proof_builder::witness_key(11))
+			),
+			Error::<Test>::UnauthorizedRevocation
+		);
+		assert_ok!(Reclaim::revoke_claim(
+			RawOrigin::Signed(2).into(),
+			owned_identifier,
+			signed_by_owner(&owner_key)
+		));
+		assert!(Reclaim::is_claim_revoked(&owned_identifier));
+	})
+}
+
+#[test]
+fn should_call_hooks_of_verified_and_revoked_claims() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(Reclaim::init(RawOrigin::Root.into(), 1));
+		assert_ok!(Reclaim::add_epoch(RawOrigin::Signed(1).into(), proof_builder::witnesses(3), 2));
+		let proof = epoch_proof("76561199601812329");
+		let identifier = claim_identifier(&proof);
+
+		// Every hook of the tuple is called, whether or not the claim is stored
+		assert_ok!(verify_as(1, &proof));
+		assert_eq!(Reclaim::verified_claims(identifier), None);
+		assert_eq!(VerifiedClaimsSeen::get(), vec![(1, identifier), (1, identifier)]);
+
+		VerifiedClaimDeposit::set(Some(0));
+		assert_ok!(Reclaim::verify_proofs(
+			RawOrigin::Signed(2).into(),
+			BoundedVec::truncate_from(vec![proof.clone()]),
+			BatchMode::AllOrNothing,
+		));
+		assert_eq!(VerifiedClaimsSeen::get().len(), 4);
+		assert_eq!(VerifiedClaimsSeen::get()[3], (2, identifier));
+
+		let signatures = revocation_signed_by(&identifier, &attesting_indices(&proof, 3));
+		assert_ok!(Reclaim::revoke_claim(RawOrigin::Signed(1).into(), identifier, signatures));
+		assert_eq!(RevokedClaimsSeen::get(), vec![identifier, identifier]);
+	})
+}
+
+#[test]
+fn should_weigh_hooks_of_verified_and_revoked_claims() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(Reclaim::init(RawOrigin::Root.into(), 1));
+		assert_ok!(Reclaim::add_epoch(RawOrigin::Signed(1).into(), proof_builder::witnesses(3), 2));
+		let proof = epoch_proof("76561199601812329");
+		let identifier = claim_identifier(&proof);
+		let hooks_weight = HOOK_WEIGHT.saturating_mul(2);
+
+		// Each hook of the tuple is declared and charged on verification
+		let checks_weight =
+			<() as WeightInfo>::verify_proof(2, MAX_WITNESSES, proof.claimInfo.size());
+		assert_eq!(
+			<Reclaim as ReclaimVerifier<u64, Proof>>::verify_proof_weight(&proof),
+			checks_weight.saturating_add(hooks_weight)
+		);
+		let post_info = verify_as(1, &proof).unwrap();
+		assert_eq!(
+			post_info.actual_weight,
+			Some(
+				Reclaim::proof_check_weight(
+					&proof,
+					ProofCheckProgress { witnesses: 3, signatures: 2 }
+				)
+				.saturating_add(hooks_weight)
+			)
+		);
+
+		// And on revocation
+		let signatures = revocation_signed_by(&identifier, &attesting_indices(&proof, 3));
+		let revoke_weight = <() as WeightInfo>::revoke_claim(signatures.len() as u32);
+		let call = crate::Call::<Test>::revoke_claim { identifier, signatures };
+		assert_eq!(call.get_dispatch_info().weight, revoke_weight.saturating_add(hooks_weight));
+	})
+}
+
+#[test]
+fn should_verify_optimistic_proofs() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		for account in [1, 2] {
+			Balances::make_free_balance_be(&account, 100);
+		}
+		assert_ok!(Reclaim::init(RawOrigin::Root.into(), 1));
+		assert_ok!(Reclaim::add_epoch(RawOrigin::Signed(1).into(), proof_builder::witnesses(3), 2));
+		let proof = epoch_proof("76561199601812329");
+		let mut forged = proof.clone();
+		forged.signedClaim.signatures[0] =
+			proof_builder::sign_claim(&forged.signedClaim.claim, &proof_builder::witness_key(99));
+		let submit = |who: u64, proof: &Proof| {
+			Reclaim::submit_optimistic(
+				RawOrigin::Signed(who).into(),
+				proof.claimInfo.clone(),
+				proof.signedClaim.clone(),
+			)
+		};
+
+		assert_noop!(submit(1, &proof), Error::<Test>::OptimisticDisabled);
+		OptimisticBond::set(Some(50));
+		let mut oversized = proof.clone();
+		oversized.claimInfo.parameters = "a".repeat(1024);
+		assert_noop!(submit(1, &oversized), Error::<Test>::ProofTooLarge);
+		// Every stored field counts towards the size of the proof
+		let mut oversized = proof.clone();
+		oversized.signedClaim.claim.owner = "a".repeat(1024);
+		assert_noop!(submit(1, &oversized), Error::<Test>::ProofTooLarge);
+		let mut oversized = proof.clone();
+		oversized.signedClaim.signatures[0] = "a".repeat(1024);
+		assert_noop!(submit(1, &oversized), Error::<Test>::ProofTooLarge);
+
+		// Proofs are accepted without signer recovery, up to the limit of their expiry block
+		assert_ok!(submit(1, &proof));
+		System::assert_last_event(
+			Event::OptimisticProofSubmitted { proof_id: 0, who: 1, expires_at: 6 }.into(),
+		);
+		assert_ok!(submit(2, &forged));
+		assert_noop!(submit(1, &proof), Error::<Test>::TooManyPendingProofs);
+		assert_eq!(Balances::reserved_balance(1), 50);
+		assert_eq!(PendingExpiries::<Test>::get(6).into_inner(), vec![0, 1]);
+
+		// Challenges must be weighed for every signature of the proof
+		assert_eq!(Reclaim::pending_proofs(1).map(|pending| pending.signatures), Some(2));
+		assert_noop!(
+			Reclaim::challenge(RawOrigin::Signed(3).into(), 1, 1),
+			Error::<Test>::SignatureCountTooLow
+		);
+
+		// Challenging an invalid proof slashes its submitter and rewards the challenger
+		assert_ok!(Reclaim::challenge(RawOrigin::Signed(3).into(), 1, 2));
+		System::assert_last_event(
+			Event::OptimisticProofSlashed {
+				proof_id: 1,
+				challenger: 3,
+				error: Error::<Test>::SignatureMismatch.into(),
+			}
+			.into(),
+		);
+		assert_eq!(Balances::reserved_balance(2), 0);
+		assert_eq!(Balances::free_balance(2), 50);
+		assert_eq!(Balances::free_balance(3), 20);
+		assert_noop!(
+			Reclaim::challenge(RawOrigin::Signed(3).into(), 1, 2),
+			Error::<Test>::PendingProofNotFound
+		);
+
+		// Unchallenged proofs are finalized once their challenge period ends
+		System::set_block_number(6);
+		Reclaim::on_initialize(6);
+		System::assert_has_event(Event::OptimisticProofFinalized { proof_id: 0 }.into());
+		assert_eq!(Reclaim::pending_proofs(0), None);
+		assert_eq!(Balances::reserved_balance(1), 0);
+		assert_eq!(VerifiedClaimsSeen::get().first().map(|(who, _)| *who), Some(1));
+
+		// A challenge can't slash while verification is paused, and finalizes valid proofs
+		assert_ok!(submit(1, &proof));
+		assert_ok!(Reclaim::pause(RawOrigin::Root.into(), PauseScope::All));
+		assert!(Reclaim::challenge(RawOrigin::Signed(3).into(), 2, 2).is_err());
+		assert_eq!(Balances::reserved_balance(1), 50);
+		// Nor is it finalized when its challenge period ends: it is rescheduled a period later
+		System::set_block_number(11);
+		Reclaim::on_initialize(11);
+		System::assert_last_event(Event::OptimisticProofDeferred { proof_id: 2 }.into());
+		assert_eq!(Reclaim::pending_proofs(2).map(|pending| pending.expires_at), Some(16));
+		assert_eq!(PendingExpiries::<Test>::get(16).into_inner(), vec![2]);
+		assert_eq!(Balances::reserved_balance(1), 50);
+
+		// And finalized optimistically once verification resumed
+		assert_ok!(Reclaim::unpause(RawOrigin::Root.into(), PauseScope::All));
+		System::set_block_number(16);
+		Reclaim::on_initialize(16);
+		System::assert_has_event(Event::OptimisticProofFinalized { proof_id: 2 }.into());
+		assert_eq!(Reclaim::pending_proofs(2), None);
+		assert_eq!(Balances::reserved_balance(1), 0);
+	})
+}
+
+#[test]
+fn should_reschedule_deferred_optimistic_proofs_in_blocks_with_room() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		Balances::make_free_balance_be(&1, 1_000);
+		assert_ok!(Reclaim::init(RawOrigin::Root.into(), 1));
+		assert_ok!(Reclaim::add_epoch(RawOrigin::Signed(1).into(), proof_builder::witnesses(3), 2));
+		OptimisticBond::set(Some(50));
+		let submit = |claim_data: &str| {
+			let proof = epoch_proof(claim_data);
+			Reclaim::submit_optimistic(
+				RawOrigin::Signed(1).into(),
+				proof.claimInfo,
+				proof.signedClaim,
+			)
+		};
+		assert_ok!(submit("first"));
+		assert_ok!(Reclaim::pause(RawOrigin::Root.into(), PauseScope::All));
+
+		// The block one period later is filled by proofs submitted meanwhile
+		System::set_block_number(6);
+		assert_ok!(Reclaim::unpause(RawOrigin::Root.into(), PauseScope::All));
+		assert_ok!(submit("second"));
+		assert_ok!(submit("third"));
+		assert_ok!(Reclaim::pause(RawOrigin::Root.into(), PauseScope::All));
+		Reclaim::on_initialize(6);
+		assert_eq!(PendingExpiries::<Test>::get(11).into_inner(), vec![1, 2]);
+		assert_eq!(PendingExpiries::<Test>::get(12).into_inner(), vec![0]);
+		assert_eq!(Reclaim::pending_proofs(0).map(|pending| pending.expires_at), Some(12));
+	})
+}
+
+#[test]
+fn should_release_bond_of_optimistic_proofs_invalidated_since_submission() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		Balances::make_free_balance_be(&1, 100);
+		assert_ok!(Reclaim::init(RawOrigin::Root.into(), 1));
+		assert_ok!(Reclaim::add_epoch(RawOrigin::Signed(1).into(), proof_builder::witnesses(3), 2));
+		OptimisticBond::set(Some(50));
+		let proof = epoch_proof("76561199601812329");
+		assert_ok!(Reclaim::submit_optimistic(
+			RawOrigin::Signed(1).into(),
+			proof.claimInfo.clone(),
+			proof.signedClaim.clone(),
+		));
+
+		// The witness was revoked after the proof was submitted
+		let attesting = attesting_indices(&proof, 3);
+		assert_ok!(Reclaim::revoke_witness(
+			RawOrigin::Root.into(),
+			proof_builder::witness(attesting[0]).address
+		));
+		assert_ok!(Reclaim::challenge(RawOrigin::Signed(3).into(), 0, 2));
+		System::assert_last_event(
+			Event::OptimisticProofDropped {
+				proof_id: 0,
+				error: Error::<Test>::RevokedWitness.into(),
+			}
+			.into(),
+		);
+		assert_eq!(Reclaim::pending_proofs(0), None);
+		assert_eq!(Balances::reserved_balance(1), 0);
+		assert_eq!(Balances::free_balance(1), 100);
+	})
+}
+
+#[test]
+fn should_drop_unchallenged_optimistic_proofs_of_revoked_witnesses() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		Balances::make_free_balance_be(&1, 100);
+		assert_ok!(Reclaim::init(RawOrigin::Root.into(), 1));
+		assert_ok!(Reclaim::add_epoch(RawOrigin::Signed(1).into(), proof_builder::witnesses(3), 2));
+		OptimisticBond::set(Some(50));
+		VerifiedClaimDeposit::set(Some(0));
+		let proof = epoch_proof("76561199601812329");
+		assert_ok!(Reclaim::submit_optimistic(
+			RawOrigin::Signed(1).into(),
+			proof.claimInfo.clone(),
+			proof.signedClaim.clone(),
+		));
+
+		// A witness expected to sign the proof is revoked during its challenge period
+		let attesting = attesting_indices(&proof, 3);
+		assert_ok!(Reclaim::revoke_witness(
+			RawOrigin::Root.into(),
+			proof_builder::witness(attesting[0]).address
+		));
+		System::set_block_number(6);
+		Reclaim::on_initialize(6);
+		System::assert_last_event(
+			Event::OptimisticProofDropped {
+				proof_id: 0,
+				error: Error::<Test>::RevokedWitness.into(),
+			}
+			.into(),
+		);
+		assert_eq!(Reclaim::pending_proofs(0), None);
+		assert_eq!(Reclaim::verified_claims(claim_identifier(&proof)), None);
+		assert_eq!(Balances::reserved_balance(1), 0);
+		assert_eq!(Balances::free_balance(1), 100);
+	})
+}
+
+#[test]
+fn should_bond_optimistic_proofs_by_encoded_size() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		Balances::make_free_balance_be(&1, 10_000);
+		assert_ok!(Reclaim::init(RawOrigin::Root.into(), 1));
+		assert_ok!(Reclaim::add_epoch(RawOrigin::Signed(1).into(), proof_builder::witnesses(3), 2));
+		OptimisticBond::set(Some(50));
+		OptimisticBondPerByte::set(2);
+		let proof = epoch_proof("76561199601812329");
+		let size = proof.encoded_size() as u64;
+
+		assert_ok!(Reclaim::submit_optimistic(
+			RawOrigin::Signed(1).into(),
+			proof.claimInfo.clone(),
+			proof.signedClaim.clone(),
+		));
+		let pending = Reclaim::pending_proofs(0).unwrap();
+		assert_eq!(pending.bond, 50 + 2 * size);
+		assert_eq!(Balances::reserved_balance(1), 50 + 2 * size);
+		assert_eq!(pending.decode_proof(), Ok(proof));
 	})
 }
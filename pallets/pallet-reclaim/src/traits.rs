@@ -1,11 +1,139 @@
-use frame_support::dispatch::DispatchResult;
+use crate::{CompleteClaimData, Witness};
+use frame_support::{
+	dispatch::{DispatchError, DispatchResult, DispatchResultWithPostInfo},
+	weights::Weight,
+};
+use scale_info::prelude::vec::Vec;
 
-pub trait ReclaimVerifier<Proof> {
-	fn verify_proof(proof: &Proof) -> DispatchResult;
+pub trait ReclaimVerifier<AccountId, Proof> {
+	/// Verifies a proof submitted by `who`, reporting the weight of the checks actually performed.
+	fn verify_proof(who: &AccountId, proof: &Proof) -> DispatchResultWithPostInfo;
+
+	/// Verifies a proof like `verify_proof`, without storing its claim with a deposit reserved
+	/// from `who`, e.g. for unsigned submissions of accounts holding no funds.
+	fn verify_proof_without_deposit(who: &AccountId, proof: &Proof) -> DispatchResultWithPostInfo;
+
+	/// Returns the worst-case weight of `verify_proof` for a proof.
+	fn verify_proof_weight(proof: &Proof) -> Weight;
+
+	/// Checks a proof submitted by `who` like `verify_proof`, without side effects: fails
+	/// wherever `verify_proof` would, e.g. on the rate limit of `who` or a revoked claim.
+	fn validate_proof(who: &AccountId, proof: &Proof) -> DispatchResult;
+
+	/// Returns the addresses of the witnesses attesting the claim of a proof.
+	fn attesting_witnesses(proof: &Proof) -> Vec<[u8; 20]>;
+
+	/// Returns whether the signatures of a witness are no longer accepted.
+	fn is_witness_revoked(address: &[u8; 20]) -> bool;
+
+	/// Returns whether the claim with `identifier` has been revoked.
+	fn is_claim_revoked(identifier: &[u8; 32]) -> bool;
+}
+
+/// No verifier: rejects every proof, without panicking on dispatch.
+impl<AccountId, Proof> ReclaimVerifier<AccountId, Proof> for () {
+	fn verify_proof(_who: &AccountId, _proof: &Proof) -> DispatchResultWithPostInfo {
+		Err(DispatchError::Other("No Reclaim verifier configured").into())
+	}
+
+	fn verify_proof_without_deposit(
+		_who: &AccountId,
+		_proof: &Proof,
+	) -> DispatchResultWithPostInfo {
+		Err(DispatchError::Other("No Reclaim verifier configured").into())
+	}
+
+	fn verify_proof_weight(_proof: &Proof) -> Weight {
+		Weight::zero()
+	}
+
+	fn validate_proof(_who: &AccountId, _proof: &Proof) -> DispatchResult {
+		Err(DispatchError::Other("No Reclaim verifier configured"))
+	}
+
+	fn attesting_witnesses(_proof: &Proof) -> Vec<[u8; 20]> {
+		Vec::new()
+	}
+
+	fn is_witness_revoked(_address: &[u8; 20]) -> bool {
+		false
+	}
+
+	fn is_claim_revoked(_identifier: &[u8; 32]) -> bool {
+		false
+	}
 }
 
-impl<Proof> ReclaimVerifier<Proof> for () {
-	fn verify_proof(_proof: &Proof) -> DispatchResult {
-		unimplemented!()
+/// Extracts the Reclaim proof carried by a call, if any.
+pub trait ProofCall<Proof> {
+	fn reclaim_proof(&self) -> Option<Proof>;
+}
+
+/// Scheme witnesses sign claims with, over the `Signature` and `PublicKey` types of the runtime.
+pub trait WitnessSignatureScheme<Signature, PublicKey> {
+	/// Returns the message witnesses sign for a claim.
+	fn message(claim: &CompleteClaimData) -> Vec<u8>;
+
+	/// Returns the address of the witness that signed `message` with the hex-encoded
+	/// `signature`, or `None` if the signature is malformed or invalid.
+	fn recover_signer(message: &[u8], signature: &str) -> Option<[u8; 20]>;
+
+	/// Returns the message witnesses sign to revoke the verified claim with `identifier`.
+	fn revocation_message(identifier: &[u8; 32]) -> Vec<u8>;
+
+	/// Returns the message the epoch source signs for the epoch `epoch_id` of `witness` requiring
+	/// `minimum_witness` signatures per claim, on the chain with `genesis_hash`.
+	fn epoch_message(
+		genesis_hash: &[u8],
+		epoch_id: u64,
+		witness: &[Witness],
+		minimum_witness: u32,
+	) -> Vec<u8>;
+}
+
+/// Hook called when a proof is verified. Implemented for tuples, so several pallets can react.
+pub trait OnProofVerified<AccountId, VerifiedClaim> {
+	/// Reacts to the verification by `who` of the claim `claim`, identified by `identifier`.
+	fn on_proof_verified(who: &AccountId, identifier: &[u8; 32], claim: &VerifiedClaim);
+
+	/// Returns the worst-case weight of `on_proof_verified`, added to the weight of every call
+	/// or hook that may verify a proof.
+	fn on_proof_verified_weight() -> Weight;
+}
+
+#[impl_trait_for_tuples::impl_for_tuples(30)]
+impl<AccountId, VerifiedClaim> OnProofVerified<AccountId, VerifiedClaim> for Tuple {
+	fn on_proof_verified(who: &AccountId, identifier: &[u8; 32], claim: &VerifiedClaim) {
+		for_tuples!( #( Tuple::on_proof_verified(who, identifier, claim); )* );
+	}
+
+	fn on_proof_verified_weight() -> Weight {
+		let mut weight = Weight::zero();
+		for_tuples!( #( weight.saturating_accrue(Tuple::on_proof_verified_weight()); )* );
+		weight
+	}
+}
+
+/// Hook called when a verified claim is revoked. Implemented for tuples, so several pallets can
+/// react.
+pub trait OnClaimRevoked<VerifiedClaim> {
+	/// Reacts to the revocation of the verified claim `claim`, stored under `identifier`.
+	fn on_claim_revoked(identifier: &[u8; 32], claim: &VerifiedClaim);
+
+	/// Returns the worst-case weight of `on_claim_revoked`, added to the weight of
+	/// `revoke_claim`.
+	fn on_claim_revoked_weight() -> Weight;
+}
+
+#[impl_trait_for_tuples::impl_for_tuples(30)]
+impl<VerifiedClaim> OnClaimRevoked<VerifiedClaim> for Tuple {
+	fn on_claim_revoked(identifier: &[u8; 32], claim: &VerifiedClaim) {
+		for_tuples!( #( Tuple::on_claim_revoked(identifier, claim); )* );
+	}
+
+	fn on_claim_revoked_weight() -> Weight {
+		let mut weight = Weight::zero();
+		for_tuples!( #( weight.saturating_accrue(Tuple::on_claim_revoked_weight()); )* );
+		weight
 	}
 }
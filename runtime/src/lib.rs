@@ -12,11 +12,13 @@ use sp_core::{crypto::KeyTypeId, OpaqueMetadata};
 use sp_runtime::{
 	create_runtime_str, generic, impl_opaque_keys,
 	traits::{
-		AccountIdLookup, BlakeTwo256, Block as BlockT, IdentifyAccount, NumberFor, One, Verify,
+		self, AccountIdLookup, BlakeTwo256, Block as BlockT, IdentifyAccount, NumberFor, One,
+		SaturatedConversion, Verify,
 	},
-	transaction_validity::{TransactionSource, TransactionValidity},
+	transaction_validity::{TransactionPriority, TransactionSource, TransactionValidity},
 	ApplyExtrinsicResult, MultiSignature,
 };
+use codec::Encode;
 use sp_std::prelude::*;
 #[cfg(feature = "std")]
 use sp_version::NativeVersion;
@@ -47,6 +49,7 @@ pub use sp_runtime::{Perbill, Permill};
 
 pub use pallet_integration_with_reclaim;
 pub use pallet_reclaim;
+use pallet_reclaim::traits::ProofCall;
 
 /// An index to a block.
 pub type BlockNumber = u32;
@@ -103,10 +106,10 @@ pub const VERSION: RuntimeVersion = RuntimeVersion {
 	//   `spec_version`, and `authoring_version` are the same between Wasm and native.
 	// This value is set to 100 to notify Polkadot-JS App (https://polkadot.js.org/apps) to use
 	//   the compatible custom types.
-	spec_version: 100,
+	spec_version: 101,
 	impl_version: 1,
 	apis: RUNTIME_API_VERSIONS,
-	transaction_version: 1,
+	transaction_version: 2,
 	state_version: 1,
 };
 
@@ -267,20 +270,135 @@ impl pallet_sudo::Config for Runtime {
 	type WeightInfo = pallet_sudo::weights::SubstrateWeight<Runtime>;
 }
 
+impl<LocalCall> frame_system::offchain::CreateSignedTransaction<LocalCall> for Runtime
+where
+	RuntimeCall: From<LocalCall>,
+{
+	fn create_transaction<C: frame_system::offchain::AppCrypto<<Signature as Verify>::Signer, Signature>>(
+		call: RuntimeCall,
+		public: <Signature as Verify>::Signer,
+		account: AccountId,
+		nonce: Nonce,
+	) -> Option<(RuntimeCall, <UncheckedExtrinsic as traits::Extrinsic>::SignaturePayload)> {
+		let tip = 0;
+		// Take the biggest period possible.
+		let period =
+			BlockHashCount::get().checked_next_power_of_two().map(|c| c / 2).unwrap_or(2) as u64;
+		let current_block = System::block_number()
+			.saturated_into::<u64>()
+			// The `System::block_number` is initialized with `n+1`,
+			// so the actual block number is `n`.
+			.saturating_sub(1);
+		let era = generic::Era::mortal(period, current_block);
+		let extra = (
+			frame_system::CheckNonZeroSender::<Runtime>::new(),
+			frame_system::CheckSpecVersion::<Runtime>::new(),
+			frame_system::CheckTxVersion::<Runtime>::new(),
+			frame_system::CheckGenesis::<Runtime>::new(),
+			frame_system::CheckEra::<Runtime>::from(era),
+			frame_system::CheckNonce::<Runtime>::from(nonce),
+			frame_system::CheckWeight::<Runtime>::new(),
+			pallet_transaction_payment::ChargeTransactionPayment::<Runtime>::from(tip),
+			pallet_reclaim::CheckReclaimProof::<Runtime>::new(),
+		);
+		let raw_payload = SignedPayload::new(call, extra).ok()?;
+		let signature = raw_payload.using_encoded(|payload| C::sign(payload, public))?;
+		let (call, extra, _) = raw_payload.deconstruct();
+		Some((call, (sp_runtime::MultiAddress::Id(account), signature, extra)))
+	}
+}
+
+impl frame_system::offchain::SigningTypes for Runtime {
+	type Public = <Signature as Verify>::Signer;
+	type Signature = Signature;
+}
+
+impl<C> frame_system::offchain::SendTransactionTypes<C> for Runtime
+where
+	RuntimeCall: From<C>,
+{
+	type Extrinsic = UncheckedExtrinsic;
+	type OverarchingCall = RuntimeCall;
+}
+
 parameter_types! {
 	pub const MaxSize: u32 = 50;
+	/// Default endpoint the Reclaim offchain worker fetches witness epochs from. Node operators
+	/// can override it through the `reclaim::epoch-source-url` offchain local storage key.
+	pub const ReclaimEpochSourceUrl: &'static str = "http://localhost:8001/epoch";
+	/// Addresses of the keys the Reclaim epoch source signs epochs with. Epochs are only synced
+	/// once the keys of the Reclaim network are listed here: while the list is empty, every
+	/// offchain worker run fails with an epoch source signature error and `sync_epoch` is
+	/// rejected, so new epochs can only be added by the owner through `add_epoch`.
+	pub ReclaimEpochSourceSigners: Vec<[u8; 20]> = reclaim_epoch_source_signers();
+	/// Extracted claim parameter that identifies the user behind a proof.
+	pub const ReclaimUniqueIdentityParameter: Option<&'static str> = Some("CLAIM_DATA");
+	/// Transaction pool priority of unsigned Reclaim verifications.
+	pub const ReclaimUnsignedPriority: TransactionPriority = TransactionPriority::max_value() / 2;
+	/// Deposit reserved for each verified claim stored by Reclaim.
+	pub const ReclaimVerifiedClaimDeposit: Option<Balance> = Some(100 * EXISTENTIAL_DEPOSIT);
+	/// Bond reserved for each optimistic Reclaim proof, slashed if the proof is invalid.
+	pub const ReclaimOptimisticBond: Option<Balance> = Some(1000 * EXISTENTIAL_DEPOSIT);
+	/// Bond reserved per byte of each optimistic Reclaim proof, on top of the base bond.
+	pub const ReclaimOptimisticBondPerByte: Balance = EXISTENTIAL_DEPOSIT;
+	/// Share of a slashed optimistic bond paid to the challenger of the invalid proof.
+	pub const ReclaimChallengerReward: Perbill = Perbill::from_percent(50);
+	/// Deposit reserved from the relayer of a verification while its claim is tracked.
+	pub const ReclaimRelayedClaimDeposit: Balance = 10 * EXISTENTIAL_DEPOSIT;
+}
+
+/// No epoch source signer is trusted until the keys of the Reclaim network are known.
+#[cfg(not(feature = "runtime-benchmarks"))]
+fn reclaim_epoch_source_signers() -> Vec<[u8; 20]> {
+	Vec::new()
+}
+
+/// Benchmarks sign the synced epoch with the generated epoch source key.
+#[cfg(feature = "runtime-benchmarks")]
+fn reclaim_epoch_source_signers() -> Vec<[u8; 20]> {
+	sp_std::vec![pallet_reclaim::proof_builder::epoch_source_signer()]
 }
 
 impl pallet_reclaim::Config for Runtime {
 	type RuntimeEvent = RuntimeEvent;
 	type Signature = sp_core::ecdsa::Signature;
 	type PublicKey = sp_core::ecdsa::Public;
+	type WitnessSignatureScheme = pallet_reclaim::schemes::EthereumWitnesses;
+	type AuthorityId = pallet_reclaim::crypto::ReclaimAuthId;
+	type EpochSourceUrl = ReclaimEpochSourceUrl;
+	type EpochSourceSigners = ReclaimEpochSourceSigners;
+	type EpochSyncInterval = ConstU32<{ 10 * MINUTES }>;
+	type MaxBatch = ConstU32<50>;
+	type InitOrigin = frame_system::EnsureRoot<AccountId>;
+	type AdminOrigin = frame_system::EnsureRoot<AccountId>;
+	type ProofAttemptWindow = ConstU32<{ 10 * MINUTES }>;
+	type MaxProofAttempts = ConstU32<20>;
+	type Currency = Balances;
+	type VerifiedClaimDeposit = ReclaimVerifiedClaimDeposit;
+	type OnProofVerified = ();
+	type OnClaimRevoked = IntegrationWithReclaim;
+	type OptimisticBond = ReclaimOptimisticBond;
+	type OptimisticBondPerByte = ReclaimOptimisticBondPerByte;
+	type ChallengePeriod = ConstU32<{ 10 * MINUTES }>;
+	type MaxPendingPerBlock = ConstU32<50>;
+	type MaxOptimisticProofSize = ConstU32<{ 16 * 1024 }>;
+	type Slash = ();
+	type ChallengerReward = ReclaimChallengerReward;
 	type WeightInfo = pallet_reclaim::weights::SubstrateWeightInfo<Runtime>;
 }
 
 impl pallet_integration_with_reclaim::Config for Runtime {
 	type RuntimeEvent = RuntimeEvent;
 	type ReclaimVerifier = pallet_reclaim::Pallet<Runtime>;
+	type VerificationTtl = ConstU32<{ 30 * DAYS }>;
+	type UniqueIdentityParameter = ReclaimUniqueIdentityParameter;
+	type IdentityAdminOrigin = frame_system::EnsureRoot<AccountId>;
+	type UnsignedPriority = ReclaimUnsignedPriority;
+	type UnsignedLongevity = ConstU64<{ 10 * MINUTES as u64 }>;
+	type AuthorizationSignature = Signature;
+	type AuthorizationSigner = <Signature as Verify>::Signer;
+	type Currency = Balances;
+	type RelayedClaimDeposit = ReclaimRelayedClaimDeposit;
 	type WeightInfo = pallet_integration_with_reclaim::weights::SubstrateWeightInfo<Runtime>;
 }
 
@@ -300,6 +418,16 @@ construct_runtime!(
 	}
 );
 
+impl ProofCall<pallet_reclaim::Proof> for RuntimeCall {
+	fn reclaim_proof(&self) -> Option<pallet_reclaim::Proof> {
+		match self {
+			RuntimeCall::Reclaim(call) => ProofCall::reclaim_proof(call),
+			RuntimeCall::IntegrationWithReclaim(call) => ProofCall::reclaim_proof(call),
+			_ => None,
+		}
+	}
+}
+
 /// The address format for describing accounts.
 pub type Address = sp_runtime::MultiAddress<AccountId, ()>;
 /// Block header type as expected by this runtime.
@@ -316,6 +444,7 @@ pub type SignedExtra = (
 	frame_system::CheckNonce<Runtime>,
 	frame_system::CheckWeight<Runtime>,
 	pallet_transaction_payment::ChargeTransactionPayment<Runtime>,
+	pallet_reclaim::CheckReclaimProof<Runtime>,
 );
 
 /// Unchecked extrinsic type as expected by this runtime.
@@ -323,6 +452,14 @@ pub type UncheckedExtrinsic =
 	generic::UncheckedExtrinsic<Address, RuntimeCall, Signature, SignedExtra>;
 /// The payload being signed in transactions.
 pub type SignedPayload = generic::SignedPayload<RuntimeCall, SignedExtra>;
+/// Storage migrations applied on runtime upgrade.
+pub type Migrations = (
+	pallet_reclaim::migrations::v1::MigrateToV1<Runtime>,
+	pallet_reclaim::migrations::v2::MigrateToV2<Runtime>,
+	pallet_integration_with_reclaim::migrations::v1::MigrateToV1<Runtime>,
+	pallet_integration_with_reclaim::migrations::v2::MigrateToV2<Runtime>,
+	pallet_integration_with_reclaim::migrations::v3::MigrateToV3<Runtime>,
+);
 /// Executive: handles dispatch to the various modules.
 pub type Executive = frame_executive::Executive<
 	Runtime,
@@ -330,6 +467,7 @@ pub type Executive = frame_executive::Executive<
 	frame_system::ChainContext<Runtime>,
 	Runtime,
 	AllPalletsWithSystem,
+	Migrations,
 >;
 
 #[cfg(feature = "runtime-benchmarks")]
@@ -0,0 +1,22 @@
+#!/usr/bin/env bash
+# This script is meant to be run on Unix/Linux based systems, on the reference hardware of the
+# chain. It regenerates the weights of the Reclaim pallets from their benchmarks.
+set -e
+
+cd "$(dirname "$0")/.."
+
+echo "*** Building the node with runtime benchmarks"
+cargo build --release -p node-template --features runtime-benchmarks
+
+for pallet in pallet_reclaim pallet_integration_with_reclaim; do
+	echo "*** Benchmarking $pallet"
+	./target/release/node-template benchmark pallet \
+		--chain dev \
+		--wasm-execution=compiled \
+		--pallet "$pallet" \
+		--extrinsic '*' \
+		--steps 50 \
+		--repeat 20 \
+		--template ./.maintain/frame-weight-template.hbs \
+		--output "./pallets/${pallet//_/-}/src/weights.rs"
+done
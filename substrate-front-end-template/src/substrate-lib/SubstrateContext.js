@@ -66,7 +66,11 @@ const connect = (state, dispatch) => {
 
   console.log(`Connected socket: ${socket}`)
   const provider = new WsProvider(socket)
-  const _api = new ApiPromise({ provider, rpc: jsonrpc })
+  const _api = new ApiPromise({
+    provider,
+    rpc: jsonrpc,
+    signedExtensions: config.CUSTOM_SIGNED_EXTENSIONS,
+  })
 
   // Set listeners for disconnection and reconnection event.
   _api.on('connected', () => {